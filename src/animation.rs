@@ -0,0 +1,73 @@
+//! Export of a run of generations as an animated GIF
+
+use crate::Universe;
+use gif::{Encoder, Frame, Repeat};
+use std::convert::TryFrom;
+
+/// White for dead cells and black for live ones, as in `to_svg`
+const PALETTE: [u8; 6] = [0xFF, 0xFF, 0xFF, 0x00, 0x00, 0x00];
+
+impl Universe {
+    /// Encode `frames` generations as a looping animated GIF, starting with
+    /// the current one, with every cell `cell_size` pixels wide and each
+    /// frame shown for `delay_cs` hundredths of a second
+    ///
+    /// The universe is ticked to make the frames and restored afterwards.
+    /// Panics if the image would be more than 65535 pixels across.
+    pub fn to_gif(&mut self, frames: u32, cell_size: u32, delay_cs: u16) -> Vec<u8> {
+        let cell_size = cell_size.max(1);
+        let pixels_across = |cells: u32| {
+            u16::try_from(cells as u64 * cell_size as u64).unwrap_or_else(|_| {
+                panic!(
+                    "a {}x{} universe at {} pixels per cell is too large for a GIF",
+                    self.width, self.height, cell_size
+                )
+            })
+        };
+        let (width, height) = (pixels_across(self.width), pixels_across(self.height));
+
+        // The history is set aside rather than copied, it is put back as is
+        let history = std::mem::take(&mut self.history);
+        let saved = self.clone();
+        self.paused = false;
+
+        let mut bytes = Vec::new();
+        {
+            let mut encoder = Encoder::new(&mut bytes, width, height, &PALETTE)
+                .expect("writing to a Vec never fails");
+            encoder
+                .set_repeat(Repeat::Infinite)
+                .expect("writing to a Vec never fails");
+            for _ in 0..frames {
+                let mut frame =
+                    Frame::from_indexed_pixels(width, height, self.pixels(cell_size), None);
+                frame.delay = delay_cs;
+                encoder
+                    .write_frame(&frame)
+                    .expect("writing to a Vec never fails");
+                self.tick();
+            }
+        }
+
+        *self = saved;
+        self.history = history;
+        bytes
+    }
+    /// One palette index per pixel in row-major order, with each cell a
+    /// `cell_size` square
+    fn pixels(&self, cell_size: u32) -> Vec<u8> {
+        let cell_size = cell_size as usize;
+        let mut pixels = Vec::with_capacity(self.cells.len() * cell_size * cell_size);
+        for row in 0..self.height {
+            let mut line = Vec::with_capacity(self.width as usize * cell_size);
+            for col in 0..self.width {
+                let alive = self.cells[self.get_index(row, col)] as u8;
+                line.extend(std::iter::repeat_n(alive, cell_size));
+            }
+            for _ in 0..cell_size {
+                pixels.extend_from_slice(&line);
+            }
+        }
+        pixels
+    }
+}
@@ -0,0 +1,120 @@
+//! Compact binary encoding of a universe
+//!
+//! The layout is a version byte, the width and height as little endian
+//! `u32`s, the generation as a little endian `u64`, and then the cells
+//! packed 32 to a little endian `u32` word in row-major order.
+//!
+//! For URLs the bytes can also be written as unpadded URL-safe base64.
+
+use crate::Universe;
+use fixedbitset::FixedBitSet;
+
+/// Bumped whenever the layout changes
+const VERSION: u8 = 1;
+/// Bytes before the packed cells
+const HEADER_LENGTH: usize = 1 + 4 + 4 + 8;
+/// The URL-safe base64 alphabet, indexed by 6-bit value
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+impl Universe {
+    /// Encode the dimensions, generation and cells as bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let words = word_count(self.width, self.height);
+        let mut bytes = Vec::with_capacity(HEADER_LENGTH + words * 4);
+        bytes.push(VERSION);
+        bytes.extend_from_slice(&self.width.to_le_bytes());
+        bytes.extend_from_slice(&self.height.to_le_bytes());
+        bytes.extend_from_slice(&self.generation.to_le_bytes());
+        for word in self.cells.as_slice().iter().take(words) {
+            bytes.extend_from_slice(&word.to_le_bytes());
+        }
+        bytes
+    }
+    /// Decode a universe encoded by `to_bytes`
+    pub fn from_bytes(data: &[u8]) -> Result<Universe, String> {
+        if data.len() < HEADER_LENGTH {
+            return Err(format!("{} bytes is too short for a universe", data.len()));
+        }
+        if data[0] != VERSION {
+            return Err(format!("unsupported universe encoding version {}", data[0]));
+        }
+        let width = u32::from_le_bytes([data[1], data[2], data[3], data[4]]);
+        let height = u32::from_le_bytes([data[5], data[6], data[7], data[8]]);
+        let mut generation = [0; 8];
+        generation.copy_from_slice(&data[9..HEADER_LENGTH]);
+        if width == 0 || height == 0 {
+            return Err(format!("invalid universe size {}x{}", width, height));
+        }
+        let size = (width as u64) * (height as u64);
+        if size > u32::MAX as u64 {
+            return Err(format!("universe size {}x{} is too large", width, height));
+        }
+
+        let cells = &data[HEADER_LENGTH..];
+        let words = word_count(width, height);
+        if cells.len() != words * 4 {
+            return Err(format!(
+                "expected {} bytes of cells for a {}x{} universe, got {}",
+                words * 4,
+                width,
+                height,
+                cells.len()
+            ));
+        }
+        let blocks = cells
+            .chunks(4)
+            .map(|word| u32::from_le_bytes([word[0], word[1], word[2], word[3]]));
+
+        let mut universe = Universe::empty(width, height);
+        universe.cells = FixedBitSet::with_capacity_and_blocks(size as usize, blocks);
+        universe.generation = u64::from_le_bytes(generation);
+        Ok(universe)
+    }
+    /// Encode the universe like `to_bytes`, as unpadded URL-safe base64
+    pub fn to_base64(&self) -> String {
+        let bytes = self.to_bytes();
+        let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+        for chunk in bytes.chunks(3) {
+            let group = chunk.iter().enumerate().fold(0u32, |group, (i, &byte)| {
+                group | (byte as u32) << (16 - 8 * i)
+            });
+            // Three bytes fill four characters, fewer bytes fill one more
+            // character than they have bytes
+            for i in 0..=chunk.len() {
+                let value = (group >> (18 - 6 * i)) & 0x3F;
+                encoded.push(BASE64_ALPHABET[value as usize] as char);
+            }
+        }
+        encoded
+    }
+    /// Decode a universe encoded by `to_base64`
+    ///
+    /// Trailing `=` padding is accepted but not required
+    pub fn from_base64(s: &str) -> Result<Universe, String> {
+        let s = s.trim_end_matches('=');
+        if s.len() % 4 == 1 {
+            return Err(format!("base64 of length {} is truncated", s.len()));
+        }
+        let mut bytes = Vec::with_capacity(s.len() / 4 * 3 + 2);
+        for chunk in s.as_bytes().chunks(4) {
+            let mut group = 0u32;
+            for (i, &c) in chunk.iter().enumerate() {
+                let value = BASE64_ALPHABET
+                    .iter()
+                    .position(|&known| known == c)
+                    .ok_or_else(|| format!("invalid character {:?} in base64", c as char))?;
+                group |= (value as u32) << (18 - 6 * i);
+            }
+            for i in 0..chunk.len() - 1 {
+                bytes.push((group >> (16 - 8 * i)) as u8);
+            }
+        }
+        Universe::from_bytes(&bytes)
+    }
+}
+
+/// Number of `u32` words holding the cells of a `width` x `height` universe
+fn word_count(width: u32, height: u32) -> usize {
+    (width as u64 * height as u64).div_ceil(32) as usize
+}
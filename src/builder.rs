@@ -0,0 +1,84 @@
+//! Chainable configuration of a new universe
+
+use crate::{BoundaryMode, Rule, Universe};
+use wasm_bindgen::prelude::*;
+
+/// Configures a universe step by step, starting from a dead 128x128
+/// Conway torus
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct UniverseBuilder {
+    width: u32,
+    height: u32,
+    rule: Rule,
+    boundary: BoundaryMode,
+    seed: Option<u64>,
+    density: f64,
+}
+
+impl UniverseBuilder {
+    /// Use a birth/survival rule other than Conway's B3/S23
+    pub fn rule(mut self, rule: Rule) -> UniverseBuilder {
+        self.rule = rule;
+        self
+    }
+}
+
+#[wasm_bindgen]
+impl UniverseBuilder {
+    pub fn new() -> UniverseBuilder {
+        UniverseBuilder {
+            width: 128,
+            height: 128,
+            rule: Rule::default(),
+            boundary: BoundaryMode::Toroidal,
+            seed: None,
+            density: 0.5,
+        }
+    }
+    pub fn width(mut self, width: u32) -> UniverseBuilder {
+        self.width = width;
+        self
+    }
+    pub fn height(mut self, height: u32) -> UniverseBuilder {
+        self.height = height;
+        self
+    }
+    /// Use a rule given as a string like `"B36/S23"`, see `Rule::parse`
+    ///
+    /// This is how JavaScript picks a rule, since `Rule` is not exported
+    pub fn rule_str(self, rule: &str) -> Result<UniverseBuilder, String> {
+        Ok(self.rule(Rule::parse(rule)?))
+    }
+    pub fn boundary(mut self, boundary: BoundaryMode) -> UniverseBuilder {
+        self.boundary = boundary;
+        self
+    }
+    /// Fill the universe randomly from this seed, see `Universe::randomize`
+    ///
+    /// Without a seed every cell starts dead
+    pub fn seed(mut self, seed: u64) -> UniverseBuilder {
+        self.seed = Some(seed);
+        self
+    }
+    /// Probability of each cell being alive when seeded, 0.5 by default
+    pub fn density(mut self, density: f64) -> UniverseBuilder {
+        self.density = density;
+        self
+    }
+    pub fn build(&self) -> Universe {
+        let mut universe = Universe::new_with_size(self.width, self.height);
+        universe.set_rule(self.rule);
+        universe.set_boundary_mode(self.boundary);
+        if let Some(seed) = self.seed {
+            universe.randomize(self.density, seed);
+        }
+        universe
+    }
+}
+
+impl Default for UniverseBuilder {
+    fn default() -> UniverseBuilder {
+        UniverseBuilder::new()
+    }
+}
@@ -0,0 +1,79 @@
+//! Run-length encoding of the cells that flipped in the last tick
+//!
+//! A delta is a sequence of run lengths, each an unsigned LEB128 varint,
+//! alternating between runs of unchanged and changed cells in row-major
+//! order. The first run is of unchanged cells and may be empty, and the
+//! trailing run of unchanged cells is left out.
+
+use crate::Universe;
+
+impl Universe {
+    /// Encode which cells flipped in the last tick, see the module docs
+    ///
+    /// Applying the delta to the previous state with `apply_delta_rle`
+    /// gives the current live cells. Only turning alive or stopping being
+    /// alive counts, so moving between dying states is left out.
+    pub fn delta_rle(&self) -> Vec<u8> {
+        let mut delta = Vec::new();
+        let mut run_start = 0;
+        let mut changed = self.flipped.ones().peekable();
+        while let Some(first) = changed.next() {
+            let mut last = first;
+            while changed.peek() == Some(&(last + 1)) {
+                last = changed.next().unwrap();
+            }
+            push_varint(&mut delta, first - run_start);
+            push_varint(&mut delta, last + 1 - first);
+            run_start = last + 1;
+        }
+        delta
+    }
+    /// Flip the cells listed by a delta from `delta_rle`
+    ///
+    /// An invalid delta leaves every cell as it was
+    pub fn apply_delta_rle(&mut self, delta: &[u8]) -> Result<(), String> {
+        let mut bytes = delta.iter();
+        let mut runs = Vec::new();
+        let mut idx = 0usize;
+        while bytes.len() > 0 {
+            let unchanged = read_varint(&mut bytes)?;
+            let changed = read_varint(&mut bytes)?;
+            let start = idx.saturating_add(unchanged);
+            idx = start.saturating_add(changed);
+            if idx > self.cells.len() {
+                return Err(format!(
+                    "delta runs past the {} cells of the universe",
+                    self.cells.len()
+                ));
+            }
+            runs.push(start..idx);
+        }
+        let before = self.cells.clone();
+        for run in runs {
+            self.cells.toggle_range(run);
+        }
+        self.clear_walls();
+        self.forget_changed(&before);
+        Ok(())
+    }
+}
+
+fn push_varint(bytes: &mut Vec<u8>, mut value: usize) {
+    while value >= 0x80 {
+        bytes.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+fn read_varint(bytes: &mut std::slice::Iter<u8>) -> Result<usize, String> {
+    let mut value = 0usize;
+    for shift in (0..usize::BITS).step_by(7) {
+        let byte = *bytes.next().ok_or("delta ends in the middle of a run")?;
+        value |= ((byte & 0x7F) as usize) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err("delta run length is too long".to_string())
+}
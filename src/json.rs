@@ -0,0 +1,53 @@
+//! Sparse JSON encoding of a universe, listing only the live cells
+//!
+//! The layout is `{"width":8,"height":8,"live":[[0,1],[1,2]]}` with each
+//! live cell given as `[row, col]`.
+
+use crate::{Universe, MAX_IMPORT_CELLS};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+struct SparseUniverse {
+    width: u32,
+    height: u32,
+    live: Vec<(u32, u32)>,
+}
+
+impl Universe {
+    /// Encode the dimensions and the position of every live cell as JSON
+    pub fn to_json(&self) -> String {
+        let sparse = SparseUniverse {
+            width: self.width,
+            height: self.height,
+            live: self.live_cells(),
+        };
+        serde_json::to_string(&sparse).expect("a universe always serializes")
+    }
+    /// Decode a universe encoded by `to_json`
+    pub fn from_json(s: &str) -> Result<Universe, String> {
+        let sparse: SparseUniverse =
+            serde_json::from_str(s).map_err(|err| format!("invalid universe JSON: {}", err))?;
+        let (width, height) = (sparse.width, sparse.height);
+        if width == 0 || height == 0 {
+            return Err(format!("invalid universe size {}x{}", width, height));
+        }
+        if (width as u64) * (height as u64) > MAX_IMPORT_CELLS {
+            return Err(format!(
+                "universe size {}x{} is over the limit of {} cells",
+                width, height, MAX_IMPORT_CELLS
+            ));
+        }
+        let mut universe = Universe::empty(width, height);
+        for (row, col) in sparse.live {
+            if row >= height || col >= width {
+                return Err(format!(
+                    "live cell ({}, {}) is outside the {}x{} universe",
+                    row, col, width, height
+                ));
+            }
+            let idx = universe.get_index(row, col);
+            universe.cells.insert(idx);
+        }
+        Ok(universe)
+    }
+}
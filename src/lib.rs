@@ -2,13 +2,34 @@ extern crate fixedbitset;
 extern crate js_sys;
 extern crate web_sys;
 
+#[cfg(feature = "gif")]
+mod animation;
+mod binary;
+mod builder;
+mod delta;
+#[cfg(feature = "json")]
+mod json;
+pub mod patterns;
+mod plaintext;
+mod reversible;
+mod rle;
 mod utils;
+mod wireworld;
 
 use fixedbitset::FixedBitSet;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use utils::{Fnv1a, SplitMix64};
 use wasm_bindgen::prelude::*;
+#[cfg(feature = "profiling")]
 use web_sys::console;
 
+pub use builder::UniverseBuilder;
+pub use reversible::ReversibleUniverse;
+pub use wireworld::{WireworldCell, WireworldUniverse};
+
 // web-sys derives a rust macro to javascript method
+#[allow(unused_macros)]
 macro_rules! log {
     ( $( $t:tt )* ) => {
         web_sys::console::log_1(&format!( $( $t )* ).into());
@@ -17,7 +38,11 @@ macro_rules! log {
 
 // Since name is string ref, we should give a lifetime
 // when defined with struct
+//
+// Timing only happens with the `profiling` feature, otherwise the timer is
+// an empty no-op that the compiler optimizes away
 pub struct Timer<'a> {
+    #[cfg_attr(not(feature = "profiling"), allow(dead_code))]
     name: &'a str,
 }
 
@@ -28,12 +53,14 @@ pub struct Timer<'a> {
 // new() executes constructor
 impl<'a> Timer<'a> {
     pub fn new(name: &'a str) -> Timer<'a> {
+        #[cfg(feature = "profiling")]
         console::time_with_label(name);
         Timer { name }
     }
 }
 
 /// drop() executes destructor
+#[cfg(feature = "profiling")]
 impl<'a> Drop for Timer<'a> {
     fn drop(&mut self) {
         console::time_end_with_label(self.name);
@@ -48,127 +75,1392 @@ pub enum Cell {
     Alive = 1,
 }
 
+impl Cell {
+    /// The opposite state
+    pub fn toggle(self) -> Cell {
+        match self {
+            Cell::Dead => Cell::Alive,
+            Cell::Alive => Cell::Dead,
+        }
+    }
+}
+
+impl From<bool> for Cell {
+    fn from(alive: bool) -> Cell {
+        if alive {
+            Cell::Alive
+        } else {
+            Cell::Dead
+        }
+    }
+}
+
+impl From<Cell> for bool {
+    fn from(cell: Cell) -> bool {
+        cell == Cell::Alive
+    }
+}
+
+/// How the universe treats cells beyond its edges
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BoundaryMode {
+    /// Edges wrap around, so the universe is a torus
+    Toroidal = 0,
+    /// Cells beyond the edges are always dead
+    Dead = 1,
+}
+
+/// Fixed starting layouts for `Universe::new_with_seed_pattern`
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SeedPattern {
+    /// Every seventh cell in row-major order, about one in seven alive
+    Sparse = 0,
+    /// Every second and every seventh cell in row-major order, as `new`
+    /// starts with, about four in seven alive
+    Dense = 1,
+    /// Alternating cells, half of them alive, starting with a live cell in
+    /// the top-left corner
+    Checkerboard = 2,
+    /// Every cell dead
+    Empty = 3,
+}
+
+/// How `insert_pattern` and `insert_glider` treat cells beyond the edges
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InsertMode {
+    /// Cells wrap around to the opposite edge
+    Wrap = 0,
+    /// Cells beyond the edges are dropped
+    Clip = 1,
+}
+
+/// Which surrounding cells count as neighbors
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Neighborhood {
+    /// All eight surrounding cells
+    Moore = 0,
+    /// Only the four orthogonally adjacent cells
+    VonNeumann = 1,
+}
+
+/// Bitwise operation for combining the cells of two universes
 #[wasm_bindgen]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BitOp {
+    /// Alive where both are alive
+    And = 0,
+    /// Alive where either is alive
+    Or = 1,
+    /// Alive where exactly one is alive
+    Xor = 2,
+}
+
+/// Birth/survival rule in B/S notation
+///
+/// `birth[n]` tells whether a dead cell with `n` live neighbors is born, and
+/// `survive[n]` whether a live cell with `n` live neighbors lives on
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rule {
+    pub birth: [bool; 9],
+    pub survive: [bool; 9],
+}
+
+impl Rule {
+    /// Parse a rule string such as `"B3/S23"` (Conway) or `"B36/S23"`
+    /// (HighLife)
+    ///
+    /// Either part may be empty, e.g. `"B2/S"` for Seeds
+    pub fn parse(s: &str) -> Result<Rule, String> {
+        let mut birth = None;
+        let mut survive = None;
+        for part in s.trim().split('/') {
+            let mut chars = part.chars();
+            let target = match chars.next() {
+                Some('B') | Some('b') => &mut birth,
+                Some('S') | Some('s') => &mut survive,
+                _ => return Err(format!("invalid rule part {:?} in {:?}", part, s)),
+            };
+            if target.is_some() {
+                return Err(format!("duplicate rule part {:?} in {:?}", part, s));
+            }
+            let mut counts = [false; 9];
+            for c in chars {
+                match c.to_digit(10) {
+                    Some(n) if n <= 8 => counts[n as usize] = true,
+                    _ => return Err(format!("invalid neighbor count {:?} in {:?}", c, s)),
+                }
+            }
+            *target = Some(counts);
+        }
+        match (birth, survive) {
+            (Some(birth), Some(survive)) => Ok(Rule { birth, survive }),
+            _ => Err(format!("rule {:?} needs both a B and an S part", s)),
+        }
+    }
+    /// Next state of a cell given its current state and live neighbor count
+    pub fn next_state(&self, alive: bool, live_neighbors: u8) -> bool {
+        let n = live_neighbors as usize;
+        if alive {
+            self.survive[n]
+        } else {
+            self.birth[n]
+        }
+    }
+}
+
+/// Conway's B3/S23
+impl Default for Rule {
+    fn default() -> Self {
+        let mut birth = [false; 9];
+        let mut survive = [false; 9];
+        birth[3] = true;
+        survive[2] = true;
+        survive[3] = true;
+        Rule { birth, survive }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "B")?;
+        for n in (0..9).filter(|&n| self.birth[n]) {
+            write!(f, "{}", n)?;
+        }
+        write!(f, "/S")?;
+        for n in (0..9).filter(|&n| self.survive[n]) {
+            write!(f, "{}", n)?;
+        }
+        Ok(())
+    }
+}
+
+/// How cells move between states on every tick
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RuleFamily {
+    /// Cells are either dead or alive, as in Conway's game
+    LifeLike,
+    /// "Generations" rules, where a live cell that fails to survive fades
+    /// through `states - 2` dying states before it is dead. Only live cells
+    /// count as neighbors, and only dead cells can be born.
+    ///
+    /// State 0 is dead, 1 is alive and 2 onwards are dying
+    Generations { states: u8 },
+    /// Brian's Brain, where a dead cell is born with exactly two live
+    /// neighbors, and a live cell always dies after one generation and
+    /// spends one more dying. The `Rule` is not used.
+    ///
+    /// States are numbered as under `Generations`, with 3 states
+    BriansBrain,
+    /// Larger than Life rules, where neighbors are counted within the
+    /// universe's range and a cell is born or survives when its count lies
+    /// in the inclusive `(min, max)` interval. The cell itself never counts.
+    ///
+    /// With a range of 1, births in `(3, 3)` and survival in `(2, 3)` is
+    /// Conway's game.
+    LargerThanLife {
+        birth: (u32, u32),
+        survive: (u32, u32),
+    },
+    /// Weighted rules, where a cell is born or survives when the weighted
+    /// sum of its live neighbors under the universe's `Kernel` lies in the
+    /// inclusive `(min, max)` interval
+    ///
+    /// With the default kernel, births in `(3, 3)` and survival in `(2, 3)`
+    /// is Conway's game.
+    Weighted {
+        birth: (i32, i32),
+        survive: (i32, i32),
+    },
+}
+
+impl RuleFamily {
+    /// Next state of a cell given its current state and live neighbor count
+    fn next_state(&self, rule: &Rule, state: u8, live_neighbors: u32) -> u8 {
+        match *self {
+            RuleFamily::LifeLike => rule.next_state(state == 1, live_neighbors as u8) as u8,
+            RuleFamily::Generations { states } => match state {
+                0 => rule.birth[live_neighbors as usize] as u8,
+                1 if rule.survive[live_neighbors as usize] => 1,
+                dying if dying + 1 < states => dying + 1,
+                _ => 0,
+            },
+            RuleFamily::BriansBrain => match state {
+                0 => (live_neighbors == 2) as u8,
+                1 => 2,
+                _ => 0,
+            },
+            RuleFamily::LargerThanLife { birth, survive } => {
+                let (min, max) = if state == 1 { survive } else { birth };
+                (min..=max).contains(&live_neighbors) as u8
+            }
+            // The weighted sum can be negative, so it is handled by the
+            // universe itself
+            RuleFamily::Weighted { .. } => unreachable!("weighted rules need the kernel"),
+        }
+    }
+}
+
+/// Neighbor offsets with a weight each, for `RuleFamily::Weighted`
+///
+/// Every entry is `(row offset, column offset, weight)`. The default is the
+/// eight cells of the Moore neighborhood, each with a weight of 1.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Kernel {
+    pub weights: Vec<(i32, i32, i32)>,
+}
+
+impl Kernel {
+    pub fn new(weights: Vec<(i32, i32, i32)>) -> Kernel {
+        Kernel { weights }
+    }
+}
+
+impl Default for Kernel {
+    fn default() -> Self {
+        let mut weights = Vec::with_capacity(8);
+        for dr in -1..=1 {
+            for dc in -1..=1 {
+                if (dr, dc) != (0, 0) {
+                    weights.push((dr, dc, 1));
+                }
+            }
+        }
+        Kernel { weights }
+    }
+}
+
+/// Number of past generations `undo` can step back through by default
+const DEFAULT_HISTORY_LIMIT: usize = 64;
+/// Longest period `period_at` looks for
+const MAX_PERIOD: u32 = 256;
+/// Rows or columns added to each edge that live cells reach, with
+/// `set_auto_expand`
+const EXPAND_MARGIN: u32 = 8;
+/// Number of cells `tick` checks for being quiet at once, one bitset word
+const QUIET_STRETCH: u32 = 32;
+
+/// Counts describing a universe and its last tick, taken together by
+/// `Universe::stats`
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+    /// Number of ticks so far, see `Universe::generation`
+    pub generation: u64,
+    /// Number of live cells
+    pub population: u32,
+    /// Number of cells born in the last tick
+    pub births: u32,
+    /// Number of cells that died in the last tick
+    pub deaths: u32,
+    /// Number of cells that changed state in the last tick, see
+    /// `Universe::cells_changed`
+    pub changed: u32,
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
 pub struct Universe {
     width: u32,
     height: u32,
     cells: FixedBitSet,
+    // Scratch buffer the next generation is computed into, kept around to
+    // avoid allocating on every tick
+    next: FixedBitSet,
+    // Cells that changed state in the last tick, including moving between
+    // dying states
+    changed: FixedBitSet,
+    // Cells that turned alive or stopped being alive in the last tick, the
+    // same as `changed` unless there are dying states
+    flipped: FixedBitSet,
+    // Wall cells never change and count as dead neighbors, or as live ones
+    // when `walls_alive` is set
+    walls: FixedBitSet,
+    walls_alive: bool,
+    // While paused `tick` does nothing, but cells can still be edited
+    paused: bool,
+    // Grow the universe whenever live cells reach its edges
+    auto_expand: bool,
+    boundary_mode: BoundaryMode,
+    insert_mode: InsertMode,
+    neighborhood: Neighborhood,
+    rule: Rule,
+    rule_family: RuleFamily,
+    // Neighborhood radius under `RuleFamily::LargerThanLife`
+    range: u32,
+    // Neighbor weights under `RuleFamily::Weighted`
+    kernel: Kernel,
+    // Full state of every cell under `RuleFamily::Generations` and
+    // `RuleFamily::BriansBrain`. `cells`
+    // stays the source of truth for which cells are alive, this only adds
+    // the dying states on top.
+    states: Vec<u8>,
+    generation: u64,
+    last_changed: u32,
+    births: u32,
+    deaths: u32,
+    // Cells born beyond the edges in the last tick, only counted with
+    // `track_escapes`, see `escaped_population`
+    escaped: u32,
+    track_escapes: bool,
+    history: VecDeque<FixedBitSet>,
+    history_limit: usize,
+    // Cells `reset` goes back to, recorded by `new`,
+    // `new_with_seed_pattern`, `random` and `randomize`, or else by the
+    // first tick
+    initial: Option<FixedBitSet>,
+    ages: Vec<u32>,
 }
 
 impl Universe {
+    /// Create a universe of the given size with every cell dead
+    fn empty(width: u32, height: u32) -> Universe {
+        let size = cell_count(width, height);
+        Universe {
+            width,
+            height,
+            cells: FixedBitSet::with_capacity(size),
+            next: FixedBitSet::with_capacity(size),
+            changed: FixedBitSet::with_capacity(size),
+            flipped: FixedBitSet::with_capacity(size),
+            walls: FixedBitSet::with_capacity(size),
+            walls_alive: false,
+            paused: false,
+            auto_expand: false,
+            boundary_mode: BoundaryMode::Toroidal,
+            insert_mode: InsertMode::Wrap,
+            neighborhood: Neighborhood::Moore,
+            rule: Rule::default(),
+            rule_family: RuleFamily::LifeLike,
+            range: 1,
+            kernel: Kernel::default(),
+            states: vec![0; size],
+            generation: 0,
+            last_changed: 0,
+            births: 0,
+            deaths: 0,
+            escaped: 0,
+            track_escapes: false,
+            history: VecDeque::new(),
+            history_limit: DEFAULT_HISTORY_LIMIT,
+            initial: None,
+            ages: vec![0; size],
+        }
+    }
+    /// Render the universe for a terminal, with live cells as green blocks
+    /// and dead cells as spaces
+    ///
+    /// Only available on native targets, to keep it out of the WebAssembly
+    /// binary
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn render_ansi(&self) -> String {
+        const GREEN: &str = "\x1b[32m";
+        const RESET: &str = "\x1b[0m";
+        let mut output = String::new();
+        for row in 0..self.height {
+            let mut colored = false;
+            for col in 0..self.width {
+                let alive = self.cells[self.get_index(row, col)];
+                if alive && !colored {
+                    output.push_str(GREEN);
+                } else if !alive && colored {
+                    output.push_str(RESET);
+                }
+                colored = alive;
+                output.push(if alive { '█' } else { ' ' });
+            }
+            output.push_str(RESET);
+            output.push('\n');
+        }
+        output
+    }
+    /// Tick, then call `f` with the updated universe, for logging or
+    /// capturing frames every generation
+    ///
+    /// Only available on native targets, since JavaScript can't pass a Rust
+    /// closure
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn tick_with(&mut self, f: &mut dyn FnMut(&Universe)) {
+        self.tick();
+        f(self);
+    }
+    /// Create an empty universe with the same rules, boundary and
+    /// neighborhood as this one
+    fn with_same_rules(&self, width: u32, height: u32) -> Universe {
+        let mut universe = Universe::new_with_size(width, height);
+        universe.boundary_mode = self.boundary_mode;
+        universe.neighborhood = self.neighborhood;
+        universe.rule = self.rule;
+        universe.rule_family = self.rule_family;
+        universe.range = self.range;
+        universe.kernel = self.kernel.clone();
+        universe
+    }
     /// Get the dead and alive values of the entire universe
     pub fn get_cells(&self) -> &FixedBitSet {
         &self.cells
     }
+    /// Get the state of a single cell, or an error when the coordinates lie
+    /// beyond the edges instead of wrapping around like `get_cell`
+    pub fn try_get_cell(&self, row: u32, column: u32) -> Result<Cell, String> {
+        if row >= self.height || column >= self.width {
+            return Err(format!(
+                "cell ({}, {}) is outside the {}x{} universe",
+                row, column, self.width, self.height
+            ));
+        }
+        Ok(Cell::from(self.cells[self.get_index(row, column)]))
+    }
+    /// Get the age of every cell, see `ages`
+    pub fn get_ages(&self) -> &[u32] {
+        &self.ages
+    }
     /// Set cells to be alive in an universe by passing the row and column
     /// of each cell as an array
     pub fn set_cells(&mut self, cells: &[(u32, u32)]) {
         for (row, col) in cells.iter().cloned() {
             let idx = self.get_index(row, col);
-            self.cells.set(idx, true);
+            self.write_cell(idx, true);
+        }
+    }
+    /// Flip each listed cell like `toggle_cell`, in order, so a cell listed
+    /// twice ends up as it was
+    pub fn toggle_cells(&mut self, cells: &[(u32, u32)]) {
+        for (row, col) in cells.iter().cloned() {
+            self.toggle_cell(row, col);
+        }
+    }
+    /// Set cells to be alive relative to an origin, like `set_cells` with
+    /// every `(row, col)` offset by `(origin_row, origin_col)`
+    ///
+    /// Cells beyond the edges wrap around or are dropped, depending on
+    /// `set_insert_mode`
+    pub fn insert_pattern(&mut self, origin_row: u32, origin_col: u32, cells: &[(u32, u32)]) {
+        for (row, col) in cells.iter().cloned() {
+            let idx = self.insert_index(
+                origin_row as u64 + row as u64,
+                origin_col as u64 + col as u64,
+            );
+            if let Some(idx) = idx {
+                self.write_cell(idx, true);
+            }
+        }
+    }
+    /// Insert a pattern, like `insert_pattern`, at every multiple of
+    /// `step_row` rows and `step_col` columns where it fits entirely
+    ///
+    /// Steps of zero are treated as one
+    pub fn tile(&mut self, pattern: &[(u32, u32)], step_row: u32, step_col: u32) {
+        let (step_row, step_col) = (step_row.max(1), step_col.max(1));
+        let pattern_height = pattern.iter().map(|&(row, _)| row + 1).max().unwrap_or(0);
+        let pattern_width = pattern.iter().map(|&(_, col)| col + 1).max().unwrap_or(0);
+        if pattern_height > self.height || pattern_width > self.width {
+            return;
+        }
+        for origin_row in (0..=self.height - pattern_height).step_by(step_row as usize) {
+            for origin_col in (0..=self.width - pattern_width).step_by(step_col as usize) {
+                self.insert_pattern(origin_row, origin_col, pattern);
+            }
+        }
+    }
+    /// Get the row and column of every live cell, in row-major order
+    pub fn live_cells(&self) -> Vec<(u32, u32)> {
+        self.live_cells_iter().collect()
+    }
+    /// Iterate over the row and column of every live cell
+    fn live_cells_iter(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        self.cells.ones().map(move |idx| self.get_coordinates(idx))
+    }
+    /// Get the row and column of every cell that changed state in the last
+    /// tick, in row-major order, see `cells_changed`
+    pub fn changed_cells(&self) -> Vec<(u32, u32)> {
+        self.changed
+            .ones()
+            .map(|idx| self.get_coordinates(idx))
+            .collect()
+    }
+    /// Get the row and column of every cell whose state differs between the
+    /// two universes, in row-major order
+    ///
+    /// Panics if the universes have different dimensions
+    pub fn diff(&self, other: &Universe) -> Vec<(u32, u32)> {
+        assert!(
+            self.width == other.width && self.height == other.height,
+            "cannot diff a {}x{} universe against a {}x{} one",
+            self.width,
+            self.height,
+            other.width,
+            other.height
+        );
+        let mut changed = self.cells.clone();
+        changed.symmetric_difference_with(&other.cells);
+        changed
+            .ones()
+            .map(|idx| self.get_coordinates(idx))
+            .collect()
+    }
+    /// Get how cells move between states on every tick
+    pub fn rule_family(&self) -> RuleFamily {
+        self.rule_family
+    }
+    /// Get the neighbor weights used by `RuleFamily::Weighted`
+    pub fn kernel(&self) -> &Kernel {
+        &self.kernel
+    }
+    /// Set the neighbor weights used by `RuleFamily::Weighted`
+    pub fn set_kernel(&mut self, kernel: Kernel) {
+        self.kernel = kernel;
+    }
+    /// Neighborhood radius used by `RuleFamily::LargerThanLife`
+    pub fn range(&self) -> u32 {
+        self.range
+    }
+    /// Set the neighborhood radius used by `RuleFamily::LargerThanLife`, at
+    /// least 1
+    pub fn set_range(&mut self, range: u32) {
+        self.range = range.max(1);
+    }
+    /// Set how cells move between states on every tick
+    ///
+    /// A Generations rule needs at least the dead and alive states, so
+    /// fewer than 2 states are bumped up to 2
+    pub fn set_rule_family(&mut self, family: RuleFamily) {
+        self.rule_family = match family {
+            RuleFamily::Generations { states } => RuleFamily::Generations {
+                states: states.max(2),
+            },
+            family => family,
+        };
+    }
+    /// Get the average `(row, col)` of the live cells, or `None` if every
+    /// cell is dead
+    ///
+    /// This averages the raw coordinates without accounting for wrapping, so
+    /// a pattern straddling a toroidal edge lands somewhere in the middle
+    pub fn centroid(&self) -> Option<(f64, f64)> {
+        let (count, row_sum, col_sum) = self
+            .live_cells_iter()
+            .fold((0u64, 0u64, 0u64), |(count, rows, cols), (row, col)| {
+                (count + 1, rows + row as u64, cols + col as u64)
+            });
+        if count == 0 {
+            return None;
+        }
+        Some((row_sum as f64 / count as f64, col_sum as f64 / count as f64))
+    }
+    /// Size of every group of live cells connected through any of their
+    /// eight surrounding cells, found by flood filling
+    ///
+    /// Groups connect across the edges of a toroidal universe
+    fn cluster_sizes(&self) -> Vec<u32> {
+        let wrap = self.boundary_mode == BoundaryMode::Toroidal;
+        let mut visited = FixedBitSet::with_capacity(self.cells.len());
+        let mut sizes = Vec::new();
+        let mut stack = Vec::new();
+        for start in self.cells.ones() {
+            if visited.put(start) {
+                continue;
+            }
+            let mut size = 0;
+            stack.push(start);
+            while let Some(idx) = stack.pop() {
+                size += 1;
+                let (row, col) = self.get_coordinates(idx);
+                for dr in -1..=1 {
+                    for dc in -1..=1 {
+                        let (r, c) = (row as i64 + dr, col as i64 + dc);
+                        let inside =
+                            r >= 0 && c >= 0 && r < self.height as i64 && c < self.width as i64;
+                        if !inside && !wrap {
+                            continue;
+                        }
+                        let (r, c) = self.wrap(r, c);
+                        let neighbor = self.get_index(r, c);
+                        if self.cells[neighbor] && !visited.put(neighbor) {
+                            stack.push(neighbor);
+                        }
+                    }
+                }
+            }
+            sizes.push(size);
         }
+        sizes
+    }
+    /// The live cells relative to the top-left of their bounds, so the
+    /// same pattern anywhere in the universe has the same shape
+    fn live_shape(&self) -> Vec<(u32, u32)> {
+        let (min_row, min_col) = match self.live_bounds() {
+            Some((min_row, min_col, _, _)) => (min_row, min_col),
+            None => return Vec::new(),
+        };
+        self.live_cells_iter()
+            .map(|(row, col)| (row - min_row, col - min_col))
+            .collect()
+    }
+    /// Get the `(dr, dc)` that moves this universe's live cells onto
+    /// `other`'s, if the live cells of both have the same shape
+    ///
+    /// Shapes are compared relative to the top-left of their bounds, so a
+    /// pattern split across a toroidal edge doesn't match its unsplit self.
+    /// Two dead universes match with no offset.
+    pub fn equals_translated(&self, other: &Universe) -> Option<(i32, i32)> {
+        if self.live_shape() != other.live_shape() {
+            return None;
+        }
+        match (self.live_bounds(), other.live_bounds()) {
+            (Some((row, col, _, _)), Some((other_row, other_col, _, _))) => {
+                Some((other_row as i32 - row as i32, other_col as i32 - col as i32))
+            }
+            _ => Some((0, 0)),
+        }
+    }
+    /// Get the extents of the live cells as
+    /// `(min_row, min_col, max_row, max_col)`, or `None` if every cell is dead
+    pub fn live_bounds(&self) -> Option<(u32, u32, u32, u32)> {
+        let mut live = self.live_cells_iter();
+        let (row, col) = live.next()?;
+        // Live cells come in row-major order, so only the columns and the
+        // last row need searching
+        let bounds = live.fold(
+            (row, col, row, col),
+            |(min_row, min_col, _, max_col), (row, col)| {
+                (min_row, min_col.min(col), row, max_col.max(col))
+            },
+        );
+        Some(bounds)
+    }
+    /// Get the birth/survival rule used by `tick`
+    pub fn rule(&self) -> Rule {
+        self.rule
+    }
+    /// Set the birth/survival rule used by `tick`
+    pub fn set_rule(&mut self, rule: Rule) {
+        self.rule = rule;
     }
     /// Gets index of current cell
     fn get_index(&self, row: u32, column: u32) -> usize {
         (row * self.width + column) as usize
     }
-    /// Counts the number of neighbors
-    fn live_neighbor_count(&self, row: u32, column: u32) -> u8 {
-        let mut count = 0;
-
-        let north = if row == 0 { self.height - 1 } else { row - 1 };
-
-        let south = if row == self.height - 1 { 0 } else { row + 1 };
-
-        let west = if column == 0 {
-            self.width - 1
-        } else {
-            column - 1
-        };
-
-        let east = if column == self.width - 1 {
+    /// Gets row and column of the cell at an index, the inverse of
+    /// `get_index`
+    fn get_coordinates(&self, idx: usize) -> (u32, u32) {
+        let idx = idx as u32;
+        (idx / self.width, idx % self.width)
+    }
+    /// Wrap coordinates that may be negative or beyond the edges around
+    /// onto the universe, as on a torus
+    pub fn wrap(&self, row: i64, column: i64) -> (u32, u32) {
+        (
+            row.rem_euclid(self.height as i64) as u32,
+            column.rem_euclid(self.width as i64) as u32,
+        )
+    }
+    /// Gets index of a cell, wrapping coordinates beyond the edges around
+    fn get_wrapped_index(&self, row: u64, column: u64) -> usize {
+        let (row, column) = self.wrap(row as i64, column as i64);
+        self.get_index(row, column)
+    }
+    /// Index of a cell placed by `insert_pattern` or `insert_glider`, or
+    /// `None` when it lies beyond the edges under `InsertMode::Clip`
+    fn insert_index(&self, row: u64, column: u64) -> Option<usize> {
+        match self.insert_mode {
+            InsertMode::Wrap => Some(self.get_wrapped_index(row, column)),
+            InsertMode::Clip if row < self.height as u64 && column < self.width as u64 => {
+                Some(self.get_index(row as u32, column as u32))
+            }
+            InsertMode::Clip => None,
+        }
+    }
+    /// Make a single cell alive or dead, keeping walls dead
+    ///
+    /// The cell stops dying, and its age starts over if it changed
+    fn write_cell(&mut self, idx: usize, alive: bool) {
+        let alive = alive && !self.walls[idx];
+        if self.cells[idx] != alive {
+            self.ages[idx] = 0;
+        }
+        self.cells.set(idx, alive);
+        self.states[idx] = 0;
+    }
+    /// Forget the ages and dying states of the cells that differ from
+    /// `before`, after an edit that changed many cells at once
+    fn forget_changed(&mut self, before: &FixedBitSet) {
+        for idx in before.symmetric_difference(&self.cells) {
+            self.ages[idx] = 0;
+            self.states[idx] = 0;
+        }
+    }
+    /// Kill any live cells an edit left on walls
+    fn clear_walls(&mut self) {
+        self.cells.difference_with(&self.walls);
+    }
+    /// Bring the buffers that mirror `cells` back in line with its size,
+    /// forgetting the history, ages, dying states, walls and the cells
+    /// `reset` goes back to
+    fn reset_cell_buffers(&mut self) {
+        let size = self.cells.len();
+        self.next = FixedBitSet::with_capacity(size);
+        self.changed = FixedBitSet::with_capacity(size);
+        self.flipped = FixedBitSet::with_capacity(size);
+        self.walls = FixedBitSet::with_capacity(size);
+        self.ages = vec![0; size];
+        self.states = vec![0; size];
+        self.history.clear();
+        self.initial = None;
+    }
+    /// Number of states a cell can be in, counting dead and alive, see
+    /// `RuleFamily`
+    fn state_count(&self) -> u8 {
+        match self.rule_family {
+            RuleFamily::Generations { states } => states,
+            RuleFamily::BriansBrain => 3,
+            _ => 2,
+        }
+    }
+    /// State of the cell at an index, see `RuleFamily`
+    fn state_at(&self, idx: usize) -> u8 {
+        if self.cells[idx] {
+            1
+        } else if self.state_count() == 2 {
             0
         } else {
-            column + 1
+            // Anything below 2 is a live cell that has since been killed
+            match self.states[idx] {
+                dying if dying >= 2 => dying,
+                _ => 0,
+            }
+        }
+    }
+    /// State of the cell in the next generation, see `RuleFamily`
+    fn next_cell_state(&self, row: u32, column: u32) -> u8 {
+        let idx = self.get_index(row, column);
+        if self.walls[idx] {
+            return 0;
+        }
+        let state = self.state_at(idx);
+        if let RuleFamily::Weighted { birth, survive } = self.rule_family {
+            let (min, max) = if state == 1 { survive } else { birth };
+            let sum = self.weighted_neighbor_sum(row, column);
+            return (min..=max).contains(&sum) as u8;
+        }
+        let live_neighbors = match self.rule_family {
+            RuleFamily::LargerThanLife { .. } => self.count_neighbors_in_range(row, column),
+            _ => self.live_neighbor_count(row, column) as u32,
         };
+        // log!(
+        //     "Cell [{}, {}] is initially {:?} and has {} live neighbors",
+        //     row,
+        //     column,
+        //     state,
+        //     live_neighbors
+        // );
+        self.rule_family
+            .next_state(&self.rule, state, live_neighbors)
+    }
+    /// Add `EXPAND_MARGIN` rows or columns beyond every edge that has live
+    /// cells on it, moving the cells along so they keep their place
+    /// relative to each other
+    ///
+    /// Walls, ages, dying states, the history and the cells `reset` goes
+    /// back to all move along with the cells
+    fn expand_at_edges(&mut self) {
+        let (min_row, min_col, max_row, max_col) = match self.live_bounds() {
+            Some(bounds) => bounds,
+            None => return,
+        };
+        let margin = |touches: bool| if touches { EXPAND_MARGIN } else { 0 };
+        let top = margin(min_row == 0);
+        let left = margin(min_col == 0);
+        let bottom = margin(max_row + 1 == self.height);
+        let right = margin(max_col + 1 == self.width);
+        if top + left + bottom + right == 0 {
+            return;
+        }
+        let old_width = self.width as usize;
+        let width = (self.width + left + right) as usize;
+        let height = (self.height + top + bottom) as usize;
+        let moved =
+            |idx: usize| (idx / old_width + top as usize) * width + idx % old_width + left as usize;
+        let moved_bits = |bits: &FixedBitSet| {
+            let mut moved_bits = FixedBitSet::with_capacity(width * height);
+            moved_bits.extend(bits.ones().map(moved));
+            moved_bits
+        };
+        let mut ages = vec![0; width * height];
+        let mut states = vec![0; width * height];
+        for idx in 0..self.cells.len() {
+            ages[moved(idx)] = self.ages[idx];
+            states[moved(idx)] = self.states[idx];
+        }
 
-        let nw = self.get_index(north, west);
-        count += self.cells[nw] as u8;
-
-        let n = self.get_index(north, column);
-        count += self.cells[n] as u8;
-
-        let ne = self.get_index(north, east);
-        count += self.cells[ne] as u8;
-
-        let w = self.get_index(row, west);
-        count += self.cells[w] as u8;
+        self.cells = moved_bits(&self.cells);
+        self.walls = moved_bits(&self.walls);
+        self.changed = moved_bits(&self.changed);
+        self.flipped = moved_bits(&self.flipped);
+        self.next = FixedBitSet::with_capacity(width * height);
+        self.history = self.history.iter().map(moved_bits).collect();
+        self.initial = self.initial.as_ref().map(moved_bits);
+        self.ages = ages;
+        self.states = states;
+        self.width = width as u32;
+        self.height = height as u32;
+    }
+    /// How far from a cell its neighbors can be, along either axis
+    fn reach(&self) -> u32 {
+        match self.rule_family {
+            RuleFamily::LargerThanLife { .. } => self.range,
+            RuleFamily::Weighted { .. } => self
+                .kernel
+                .weights
+                .iter()
+                .map(|&(dr, dc, _)| dr.unsigned_abs().max(dc.unsigned_abs()))
+                .max()
+                .unwrap_or(0),
+            _ => 1,
+        }
+    }
+    /// Whether a dead cell outside the universe, at coordinates relative to
+    /// its top-left, would be born in the next generation if the universe
+    /// went on beyond its edges
+    fn born_beyond_edges(&self, row: i64, column: i64) -> bool {
+        let is_live = |dr: i64, dc: i64| {
+            let (r, c) = (row + dr, column + dc);
+            r >= 0
+                && c >= 0
+                && r < self.height as i64
+                && c < self.width as i64
+                && self.counts_as_live(self.get_index(r as u32, c as u32))
+        };
+        if let RuleFamily::Weighted { birth, .. } = self.rule_family {
+            let sum: i32 = self
+                .kernel
+                .weights
+                .iter()
+                .filter(|&&(dr, dc, _)| is_live(dr as i64, dc as i64))
+                .map(|&(_, _, weight)| weight)
+                .sum();
+            return (birth.0..=birth.1).contains(&sum);
+        }
+        let reach = self.reach() as i64;
+        let mut live_neighbors = 0;
+        for dr in -reach..=reach {
+            for dc in -reach..=reach {
+                if (dr == 0 && dc == 0)
+                    || (self.neighborhood == Neighborhood::VonNeumann
+                        && dr.abs() + dc.abs() > reach)
+                {
+                    continue;
+                }
+                live_neighbors += is_live(dr, dc) as u32;
+            }
+        }
+        self.rule_family.next_state(&self.rule, 0, live_neighbors) == 1
+    }
+    /// Number of cells beyond the edges that the next generation would give
+    /// birth to, were the universe unbounded
+    ///
+    /// Only the band of cells within reach of the universe is checked,
+    /// anything further out cannot have live neighbors
+    fn count_births_beyond_edges(&self) -> u32 {
+        let reach = self.reach() as i64;
+        let (height, width) = (self.height as i64, self.width as i64);
+        let mut count = 0;
+        for row in -reach..height + reach {
+            // Only the columns beyond the edges for rows inside the universe
+            let columns = if (0..height).contains(&row) {
+                (-reach..0).chain(width..width + reach)
+            } else {
+                (-reach..width + reach).chain(0..0)
+            };
+            for column in columns {
+                count += self.born_beyond_edges(row, column) as u32;
+            }
+        }
+        count
+    }
+    /// The live cells of the next generation, leaving the universe as is
+    fn next_generation(&self) -> FixedBitSet {
+        let mut next = FixedBitSet::with_capacity(self.cells.len());
+        for row in 0..self.height {
+            for col in 0..self.width {
+                if self.next_cell_state(row, col) == 1 {
+                    next.insert(self.get_index(row, col));
+                }
+            }
+        }
+        next
+    }
+    /// Write a cell of the next generation and update its age and whether
+    /// it changed
+    fn set_next(&mut self, idx: usize, state: u8) {
+        self.changed.set(idx, self.state_at(idx) != state);
+        let alive = state == 1;
+        self.next.set(idx, alive);
+        self.states[idx] = state;
+        self.ages[idx] = match (self.cells[idx], alive) {
+            (true, true) => self.ages[idx].saturating_add(1),
+            (false, true) => 1,
+            (_, false) => 0,
+        };
+    }
+    /// Whether a dead cell without live neighbors is sure to stay dead, so
+    /// quiet stretches of the universe can be skipped
+    ///
+    /// That needs a two-state rule without births from zero neighbors, and
+    /// walls that count as dead
+    fn can_skip_quiet_cells(&self) -> bool {
+        self.rule_family == RuleFamily::LifeLike && !self.rule.birth[0] && !self.walls_alive
+    }
+    /// Whether the cells of a row from `start` up to `end`, and all of their
+    /// neighbors, are dead
+    ///
+    /// The rows are checked with whole words at a time, so a stretch one
+    /// word long costs about as much as a single cell's neighbor count
+    fn is_quiet(&self, row: u32, start: u32, end: u32) -> bool {
+        let wrap = self.boundary_mode == BoundaryMode::Toroidal;
+        let (north, west) = self.wrap(row as i64 - 1, start as i64 - 1);
+        let (south, east) = self.wrap(row as i64 + 1, end as i64);
+        let rows = [
+            Some(north).filter(|_| wrap || row > 0),
+            Some(row),
+            Some(south).filter(|_| wrap || row + 1 < self.height),
+        ];
+        let columns = [
+            Some(west).filter(|_| wrap || start > 0),
+            Some(east).filter(|_| wrap || end < self.width),
+        ];
+        rows.iter().flatten().all(|&r| {
+            let first = self.get_index(r, start);
+            self.cells.count_ones(first..first + (end - start) as usize) == 0
+                && columns
+                    .iter()
+                    .flatten()
+                    .all(|&c| !self.cells[self.get_index(r, c)])
+        })
+    }
+    /// Compute the next generation one cell at a time, skipping word-long
+    /// quiet stretches
+    #[cfg(not(feature = "rayon"))]
+    fn compute_next_serial(&mut self) {
+        let skip_quiet = self.can_skip_quiet_cells();
+        for row in 0..self.height {
+            for start in (0..self.width).step_by(QUIET_STRETCH as usize) {
+                let end = self.width.min(start + QUIET_STRETCH);
+                if skip_quiet && self.is_quiet(row, start, end) {
+                    // Exactly what `set_next` does for a dead cell staying
+                    // dead, a stretch at a time
+                    let first = self.get_index(row, start);
+                    let stretch = first..first + (end - start) as usize;
+                    self.next.set_range(stretch.clone(), false);
+                    self.changed.set_range(stretch.clone(), false);
+                    self.states[stretch.clone()].fill(0);
+                    self.ages[stretch].fill(0);
+                    continue;
+                }
+                for col in start..end {
+                    let idx = self.get_index(row, col);
+                    let state = self.next_cell_state(row, col);
+                    self.set_next(idx, state);
+                    // log!("     it becomes {:?}", self.next[idx]);
+                }
+            }
+        }
+    }
+    /// Compute the next generation with the rows split across threads
+    ///
+    /// Each row is written into its own slice of a plain buffer, which is
+    /// merged into the bitset afterwards
+    #[cfg(feature = "rayon")]
+    fn compute_next_parallel(&mut self) {
+        use rayon::prelude::*;
 
-        let e = self.get_index(row, east);
-        count += self.cells[e] as u8;
+        let skip_quiet = self.can_skip_quiet_cells();
+        let mut states = vec![0; (self.width * self.height) as usize];
+        states
+            .par_chunks_mut(self.width as usize)
+            .enumerate()
+            .for_each(|(row, line)| {
+                let row = row as u32;
+                for start in (0..self.width).step_by(QUIET_STRETCH as usize) {
+                    let end = self.width.min(start + QUIET_STRETCH);
+                    // Quiet stretches keep their dead state of 0
+                    if skip_quiet && self.is_quiet(row, start, end) {
+                        continue;
+                    }
+                    for col in start..end {
+                        line[col as usize] = self.next_cell_state(row, col);
+                    }
+                }
+            });
+        for (idx, state) in states.into_iter().enumerate() {
+            self.set_next(idx, state);
+        }
+    }
+    /// Move every cell, with its age and dying state, to the coordinates
+    /// given by `map`, keeping the dimensions
+    ///
+    /// Cells moved onto a wall are dropped
+    fn remap<F: Fn(&Universe, u32, u32) -> (u32, u32)>(&mut self, map: F) {
+        let size = self.cells.len();
+        let mut cells = FixedBitSet::with_capacity(size);
+        let mut ages = vec![0; size];
+        let mut states = vec![0; size];
+        for idx in 0..size {
+            let (row, col) = self.get_coordinates(idx);
+            let (row, col) = map(self, row, col);
+            let moved = self.get_index(row, col);
+            if !self.walls[moved] {
+                cells.set(moved, self.cells[idx]);
+                ages[moved] = self.ages[idx];
+                states[moved] = self.states[idx];
+            }
+        }
+        self.cells = cells;
+        self.ages = ages;
+        self.states = states;
+    }
+    /// Counts the number of neighbors
+    ///
+    /// In `BoundaryMode::Toroidal` the edges wrap around, while in
+    /// `BoundaryMode::Dead` anything beyond the edge counts as dead. Which
+    /// cells are neighbors depends on the `Neighborhood`.
+    pub fn live_neighbor_count(&self, row: u32, column: u32) -> u8 {
+        let mut count = 0;
+        self.for_each_neighbor(row, column, |idx| count += self.counts_as_live(idx) as u8);
+        count
+    }
+    /// Whether the cell at an index counts as a live neighbor
+    fn counts_as_live(&self, idx: usize) -> bool {
+        if self.walls[idx] {
+            self.walls_alive
+        } else {
+            self.cells[idx]
+        }
+    }
+    /// Call `visit` with the index of every neighbor of a cell, following
+    /// the boundary mode and neighborhood
+    fn for_each_neighbor<F: FnMut(usize)>(&self, row: u32, column: u32, mut visit: F) {
+        let wrap = self.boundary_mode == BoundaryMode::Toroidal;
 
-        let sw = self.get_index(south, west);
-        count += self.cells[sw] as u8;
+        // Neighbors that only exist by wrapping around are dropped when the
+        // edges are dead
+        let (north, west) = self.wrap(row as i64 - 1, column as i64 - 1);
+        let (south, east) = self.wrap(row as i64 + 1, column as i64 + 1);
+        let north = Some(north).filter(|_| wrap || row > 0);
+        let south = Some(south).filter(|_| wrap || row + 1 < self.height);
+        let west = Some(west).filter(|_| wrap || column > 0);
+        let east = Some(east).filter(|_| wrap || column + 1 < self.width);
 
-        let s = self.get_index(south, column);
-        count += self.cells[s] as u8;
+        // On a universe only one or two cells across, wrapping makes
+        // several directions land on the same row or column, which must
+        // only be counted once and never be the cell itself
+        let rows = distinct_lines(north, row, south);
+        let columns = distinct_lines(west, column, east);
+        for (i, neighbor_row) in rows.iter().enumerate() {
+            for (j, neighbor_col) in columns.iter().enumerate() {
+                // Skip the cell itself, and the diagonals when only
+                // orthogonal neighbors count
+                let orthogonal = i == 1 || j == 1;
+                if (i == 1 && j == 1)
+                    || (!orthogonal && self.neighborhood == Neighborhood::VonNeumann)
+                {
+                    continue;
+                }
+                if let (Some(r), Some(c)) = (neighbor_row, neighbor_col) {
+                    visit(self.get_index(*r, *c));
+                }
+            }
+        }
+    }
+    /// Counts the live cells within the universe's range of a cell, as used
+    /// by `RuleFamily::LargerThanLife`
+    ///
+    /// Under `Neighborhood::Moore` that is every cell within the Chebyshev
+    /// distance `range`, under `Neighborhood::VonNeumann` within the
+    /// Manhattan distance. Edges and walls behave as in
+    /// `live_neighbor_count`, and a cell reached twice by wrapping around a
+    /// small universe only counts once.
+    pub fn count_neighbors_in_range(&self, row: u32, column: u32) -> u32 {
+        let rows = self.lines_in_range(row, self.height);
+        let columns = self.lines_in_range(column, self.width);
+        let mut count = 0;
+        for &(neighbor_row, row_distance) in rows.iter() {
+            for &(neighbor_col, col_distance) in columns.iter() {
+                if (row_distance == 0 && col_distance == 0)
+                    || (self.neighborhood == Neighborhood::VonNeumann
+                        && row_distance + col_distance > self.range)
+                {
+                    continue;
+                }
+                count += self.counts_as_live(self.get_index(neighbor_row, neighbor_col)) as u32;
+            }
+        }
+        count
+    }
+    /// Sum of the weights of the kernel's offsets from a cell that hold a
+    /// live cell, as used by `RuleFamily::Weighted`
+    ///
+    /// Offsets wrap around the edges in `BoundaryMode::Toroidal` and count
+    /// as dead beyond them in `BoundaryMode::Dead`, and walls behave as in
+    /// `live_neighbor_count`. Each entry of the kernel counts separately,
+    /// even when several land on the same cell.
+    pub fn weighted_neighbor_sum(&self, row: u32, column: u32) -> i32 {
+        let wrap = self.boundary_mode == BoundaryMode::Toroidal;
+        let mut sum = 0;
+        for &(dr, dc, weight) in self.kernel.weights.iter() {
+            let (r, c) = (row as i64 + dr as i64, column as i64 + dc as i64);
+            let inside = r >= 0 && c >= 0 && r < self.height as i64 && c < self.width as i64;
+            if !inside && !wrap {
+                continue;
+            }
+            let (r, c) = self.wrap(r, c);
+            if self.counts_as_live(self.get_index(r, c)) {
+                sum += weight;
+            }
+        }
+        sum
+    }
+    /// The distinct rows (or columns) within the range of `at` on an axis of
+    /// `length` lines, each with its distance from `at`
+    fn lines_in_range(&self, at: u32, length: u32) -> Vec<(u32, u32)> {
+        let wrap = self.boundary_mode == BoundaryMode::Toroidal;
+        let mut lines = vec![(at, 0)];
+        for distance in 1..=self.range.min(length) {
+            let before = if distance <= at {
+                Some(at - distance)
+            } else if wrap {
+                Some((at + length - distance) % length)
+            } else {
+                None
+            };
+            let after = if at + distance < length {
+                Some(at + distance)
+            } else if wrap {
+                Some((at + distance) % length)
+            } else {
+                None
+            };
+            for line in [before, after].iter().flatten() {
+                if lines.iter().all(|&(seen, _)| seen != *line) {
+                    lines.push((*line, distance));
+                }
+            }
+        }
+        lines
+    }
+}
 
-        let se = self.get_index(south, east);
-        count += self.cells[se] as u8;
+/// Most cells a pattern read by `from_rle` or `from_json` may have, as many
+/// as a 4096x4096 universe
+///
+/// Every cell also takes an age and a state, so much bigger headers would
+/// run a wasm module out of memory
+pub(crate) const MAX_IMPORT_CELLS: u64 = 1 << 24;
 
-        count
+/// Number of cells in a `width` x `height` grid
+///
+/// Panics when there are more than `u32::MAX`, too many to index
+pub(crate) fn cell_count(width: u32, height: u32) -> usize {
+    match width.checked_mul(height) {
+        Some(count) => count as usize,
+        None => panic!("a {}x{} universe has too many cells", width, height),
     }
 }
 
+/// The rows (or columns) before, at and after a cell, with the before and
+/// after ones dropped when they repeat an earlier one
+pub(crate) fn distinct_lines(before: Option<u32>, at: u32, after: Option<u32>) -> [Option<u32>; 3] {
+    let before = before.filter(|&line| line != at);
+    let after = after.filter(|&line| line != at && Some(line) != before);
+    [before, Some(at), after]
+}
+
 #[wasm_bindgen]
 impl Universe {
     /// Constructor
     ///
     /// Initialize the field
     pub fn new() -> Universe {
-        let width = 128;
-        let height = 128;
-        let size = (width * height) as usize;
-        let mut cells = FixedBitSet::with_capacity(size);
-        for i in 0..size {
-            if i % 2 == 0 || i % 7 == 0 {
-                cells.set(i, true);
-            } else {
-                cells.set(i, false);
-            }
+        Universe::new_with_seed_pattern(128, 128, SeedPattern::Dense)
+    }
+    /// Create a universe of the given size starting with one of the fixed
+    /// seed patterns, which is what `reset` goes back to
+    ///
+    /// A zero width or height is bumped up to 1
+    pub fn new_with_seed_pattern(width: u32, height: u32, pattern: SeedPattern) -> Universe {
+        let mut universe = Universe::new_with_size(width, height);
+        for i in 0..universe.cells.len() {
+            let (row, col) = universe.get_coordinates(i);
+            let alive = match pattern {
+                SeedPattern::Sparse => i % 7 == 0,
+                SeedPattern::Dense => i % 2 == 0 || i % 7 == 0,
+                SeedPattern::Checkerboard => (row + col) % 2 == 0,
+                SeedPattern::Empty => false,
+            };
+            universe.cells.set(i, alive);
         }
-        Universe {
-            width,
-            height,
-            cells,
+        universe.initial = Some(universe.cells.clone());
+        universe
+    }
+    /// Create a universe of the given size with every cell dead
+    ///
+    /// A zero width or height is bumped up to 1
+    pub fn new_with_size(width: u32, height: u32) -> Universe {
+        Universe::empty(width.max(1), height.max(1))
+    }
+    /// Log panics to the browser console instead of a bare `unreachable`
+    ///
+    /// Call once before anything else. Does nothing unless the
+    /// `console_error_panic_hook` feature is enabled.
+    pub fn init_panic_hook() {
+        utils::set_panic_hook();
+    }
+    /// Create a universe with randomly alive cells
+    ///
+    /// The same seed always yields the same board. A zero width or height
+    /// is bumped up to 1.
+    pub fn random(width: u32, height: u32, seed: u64) -> Universe {
+        let mut universe = Universe::new_with_size(width, height);
+        let mut rng = SplitMix64::new(seed);
+        for i in 0..universe.cells.len() {
+            universe.cells.set(i, rng.next_bool());
         }
+        universe.initial = Some(universe.cells.clone());
+        universe
+    }
+    /// Run `trials` random universes, seeded with `base_seed`,
+    /// `base_seed + 1` and so on, for up to `max_steps` ticks each
+    ///
+    /// Returns the seeds of those that settled into a non-empty still life,
+    /// where a tick changes nothing. Pass a seed to `random` with the same
+    /// size to get the universe back.
+    pub fn search_soups(
+        width: u32,
+        height: u32,
+        trials: u32,
+        max_steps: u32,
+        base_seed: u64,
+    ) -> Vec<u64> {
+        (0..trials as u64)
+            .map(|trial| base_seed.wrapping_add(trial))
+            .filter(|&seed| {
+                let mut soup = Universe::random(width, height, seed);
+                soup.set_history_limit(0);
+                soup.run_until_stable(max_steps);
+                soup.is_stable() && soup.population() > 0
+            })
+            .collect()
     }
     /// Set the width of the universe
     ///
-    /// Resets all cells to the dead state
+    /// Resets all cells to the dead state and the generation to zero. A zero
+    /// width is bumped up to 1.
     pub fn set_width(&mut self, width: u32) {
-        self.width = width;
-        let mut cells = self.cells.clone();
-        for idx in 0..width * self.height {
-            cells.set(idx as usize, false);
-        }
-        self.cells = cells;
+        self.width = width.max(1);
+        self.generation = 0;
+        self.cells = FixedBitSet::with_capacity(cell_count(self.width, self.height));
+        self.reset_cell_buffers();
     }
     /// Set the height of the universe
     ///
-    /// Resets all cells to the dead state
+    /// Resets all cells to the dead state and the generation to zero. A zero
+    /// height is bumped up to 1.
     pub fn set_height(&mut self, height: u32) {
-        self.height = height;
-        let mut cells = self.cells.clone();
-        for idx in 0..height * self.width {
-            cells.set(idx as usize, false);
+        self.height = height.max(1);
+        self.generation = 0;
+        self.cells = FixedBitSet::with_capacity(cell_count(self.width, self.height));
+        self.reset_cell_buffers();
+    }
+    /// Resize the universe, keeping every live cell that still fits
+    ///
+    /// Cells stay at the same row and column, so growing adds dead cells to
+    /// the bottom and right, and shrinking drops those beyond the new edges.
+    /// Dying cells of a Generations rule are lost, and a zero width or
+    /// height is bumped up to 1.
+    pub fn resize(&mut self, width: u32, height: u32) {
+        let (width, height) = (width.max(1), height.max(1));
+        let mut cells = FixedBitSet::with_capacity(cell_count(width, height));
+        for (row, col) in self.live_cells_iter() {
+            if row < height && col < width {
+                cells.insert((row * width + col) as usize);
+            }
         }
+        self.width = width;
+        self.height = height;
         self.cells = cells;
+        self.reset_cell_buffers();
     }
     /// Convert struct to string to render in Javascript
     pub fn render(&self) -> String {
         self.to_string()
     }
+    /// Render the universe as text, one line per row, with the given
+    /// symbols for dead and alive cells
+    pub fn render_with(&self, dead: char, alive: char) -> String {
+        // Each bit of the bitset is a single cell, so walk the grid
+        // row by row instead of chunking the packed u32 blocks
+        let mut rendered = String::new();
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let idx = self.get_index(row, col);
+                rendered.push(if self.cells[idx] { alive } else { dead });
+            }
+            rendered.push('\n');
+        }
+        rendered
+    }
+    /// Render the universe like `render`, surrounded by `margin` rows and
+    /// columns of dead cells on every side
+    ///
+    /// This shows the dead border that cells see beyond the edges in
+    /// `BoundaryMode::Dead`
+    pub fn render_padded(&self, margin: u32) -> String {
+        const DEAD: char = '◻';
+        let padded_width = (self.width + 2 * margin) as usize;
+        let blank_line: String = std::iter::repeat_n(DEAD, padded_width)
+            .chain(Some('\n'))
+            .collect();
+        let side: String = std::iter::repeat_n(DEAD, margin as usize).collect();
+        let mut rendered = blank_line.repeat(margin as usize);
+        for line in self.render_with(DEAD, '◼').lines() {
+            rendered.push_str(&side);
+            rendered.push_str(line);
+            rendered.push_str(&side);
+            rendered.push('\n');
+        }
+        rendered.push_str(&blank_line.repeat(margin as usize));
+        rendered
+    }
+    /// Render the universe as an SVG image with a square of `cell_size`
+    /// pixels for every live cell, on a white background
+    pub fn to_svg(&self, cell_size: u32) -> String {
+        let width = self.width * cell_size;
+        let height = self.height * cell_size;
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n",
+            w = width,
+            h = height
+        );
+        svg.push_str("<rect width=\"100%\" height=\"100%\" fill=\"#FFFFFF\"/>\n");
+        for (row, col) in self.live_cells_iter() {
+            svg.push_str(&format!(
+                "<rect x=\"{}\" y=\"{}\" width=\"{s}\" height=\"{s}\" fill=\"#000000\"/>\n",
+                col * cell_size,
+                row * cell_size,
+                s = cell_size
+            ));
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
     /// Tick function that determines the next tick (judging live/death of the
     /// given cell by the rule from "game of life")
     ///
@@ -185,43 +1477,279 @@ impl Universe {
     /// revives, as if by reproduction
     ///
     /// All other cells remain in the same state.
+    ///
+    /// The above is Conway's B3/S23, the default rule. Another rule can be
+    /// chosen with `set_rule`.
+    ///
+    /// Does nothing while the universe is paused.
     pub fn tick(&mut self) {
+        if self.paused {
+            return;
+        }
+        if self.auto_expand {
+            self.expand_at_edges();
+        }
+        if self.initial.is_none() {
+            self.initial = Some(self.cells.clone());
+        }
         let _timer = Timer::new("Universe::tick");
-        let mut next = {
-            let _timer = Timer::new("Allocate next cells");
-            self.cells.clone()
+        self.escaped = match self.boundary_mode {
+            BoundaryMode::Dead if self.track_escapes => self.count_births_beyond_edges(),
+            _ => 0,
         };
         {
             let _timer = Timer::new("New Generation");
-            for row in 0..self.height {
-                for col in 0..self.width {
-                    let idx = self.get_index(row, col);
-                    let cell = self.cells[idx];
-                    let live_neighbors = self.live_neighbor_count(row, col);
-                    // log!(
-                    //     "Cell [{}, {}] is initially {:?} and has {} live neighbors",
-                    //     row,
-                    //     col,
-                    //     cell,
-                    //     live_neighbors
-                    // );
-                    next.set(
-                        idx,
-                        match (cell, live_neighbors) {
-                            (true, x) if x < 2 => false,
-                            (true, 2) | (true, 3) => true,
-                            (true, x) if x > 3 => false,
-                            (false, 3) => true,
-                            (otherwise, _) => otherwise,
-                        },
-                    );
-                    // log!("     it becomes {:?}", next[idx]);
-                }
+            #[cfg(not(feature = "rayon"))]
+            self.compute_next_serial();
+            #[cfg(feature = "rayon")]
+            self.compute_next_parallel();
+        }
+        self.flipped.clone_from(&self.next);
+        self.flipped.symmetric_difference_with(&self.cells);
+        self.births = self.next.difference(&self.cells).count() as u32;
+        self.deaths = self.cells.difference(&self.next).count() as u32;
+        self.last_changed = self.changed.count_ones(..) as u32;
+        // Swap the buffers, leaving the old cells in `next`
+        std::mem::swap(&mut self.cells, &mut self.next);
+        if self.history_limit > 0 {
+            // Once the history is full, the oldest entry's buffer is reused
+            // as the next scratch buffer
+            let scratch = if self.history.len() == self.history_limit {
+                self.history.pop_front().unwrap()
+            } else {
+                FixedBitSet::with_capacity(self.cells.len())
+            };
+            let previous = std::mem::replace(&mut self.next, scratch);
+            self.history.push_back(previous);
+        }
+        self.generation += 1;
+    }
+    /// Tick, then return what `render` gives, saving a call across the
+    /// JavaScript boundary every frame
+    pub fn tick_and_render(&mut self) -> String {
+        self.tick();
+        self.render()
+    }
+    /// Advance the given number of generations in a single call
+    pub fn tick_many(&mut self, steps: u32) {
+        for _ in 0..steps {
+            self.tick();
+        }
+    }
+    /// Tick until a tick changes nothing, or `max_steps` ticks have passed
+    ///
+    /// Returns the number of ticks taken, including the final unchanging one
+    pub fn run_until_stable(&mut self, max_steps: u32) -> u32 {
+        if self.paused {
+            return 0;
+        }
+        for step in 1..=max_steps {
+            self.tick();
+            if self.is_stable() {
+                return step;
+            }
+        }
+        max_steps
+    }
+    /// Write the next generation of a one-dimensional elementary automaton
+    /// under Wolfram's `rule` number
+    ///
+    /// Each row is one generation of a line of `width` cells, starting from
+    /// row 0: generation `n` is written to row `n` until the last row is
+    /// reached, after which every row scrolls up by one. A universe one row
+    /// tall keeps only the latest generation. A cell's next state
+    /// is bit `4 * left + 2 * center + right` of `rule`, with the edges
+    /// following the boundary mode. Seed the first generation with cells in
+    /// row 0, like a single center cell.
+    pub fn step_elementary(&mut self, rule: u8) {
+        let width = self.width as usize;
+        let (last, next) = if self.height < 2 {
+            // A single row is overwritten in place
+            (0, 0)
+        } else if self.generation + 1 < self.height as u64 {
+            (self.generation as usize, self.generation as usize + 1)
+        } else {
+            // Scroll up to make room for the new row at the bottom
+            for idx in width..self.cells.len() {
+                let alive = self.cells[idx];
+                self.write_cell(idx - width, alive);
             }
+            (self.height as usize - 2, self.height as usize - 1)
+        };
+        let current: Vec<bool> = (0..width)
+            .map(|col| self.cells[last * width + col])
+            .collect();
+        let wrap = self.boundary_mode == BoundaryMode::Toroidal;
+        for col in 0..width {
+            let left = match col {
+                0 => wrap && current[width - 1],
+                _ => current[col - 1],
+            };
+            let right = if col + 1 == width {
+                wrap && current[0]
+            } else {
+                current[col + 1]
+            };
+            let pattern = (left as u8) << 2 | (current[col] as u8) << 1 | right as u8;
+            self.write_cell(next * width + col, rule >> pattern & 1 == 1);
         }
-        // Renew by vector
-        let _timer = Timer::new("Free old cells");
-        self.cells = next;
+        self.generation += 1;
+    }
+    /// Live neighbor count of every cell, one byte per cell in row-major
+    /// order, as given by `live_neighbor_count`
+    ///
+    /// Rather than counting around every cell, each live cell adds itself to
+    /// its neighbors, which is much faster on sparse universes
+    pub fn neighbor_counts(&self) -> Vec<u8> {
+        let mut counts = vec![0; self.cells.len()];
+        for idx in self.cells.union(&self.walls) {
+            if self.counts_as_live(idx) {
+                let (row, col) = self.get_coordinates(idx);
+                self.for_each_neighbor(row, col, |neighbor| counts[neighbor] += 1);
+            }
+        }
+        counts
+    }
+    /// Number of cells that changed state in the last tick
+    ///
+    /// Under a Generations rule or Brian's Brain, a cell moving on to its
+    /// next dying state counts as a change too
+    pub fn cells_changed(&self) -> u32 {
+        self.last_changed
+    }
+    /// Index of every cell that changed state in the last tick, in
+    /// row-major order, so only those need redrawing
+    pub fn changed_indices(&self) -> Vec<u32> {
+        self.changed.ones().map(|idx| idx as u32).collect()
+    }
+    /// Number of cells born in the last tick
+    pub fn last_births(&self) -> u32 {
+        self.births
+    }
+    /// Number of cells that died in the last tick
+    pub fn last_deaths(&self) -> u32 {
+        self.deaths
+    }
+    /// Number of cells lost off the edges in the last tick under
+    /// `BoundaryMode::Dead`, the ones that would have been born just beyond
+    /// the edges had the universe gone on
+    ///
+    /// A glider leaving the universe shows up here while it breaks up
+    /// against the edge. Only counted after `set_track_escapes(true)`, and
+    /// always 0 in `BoundaryMode::Toroidal`.
+    pub fn escaped_population(&self) -> u32 {
+        self.escaped
+    }
+    pub fn track_escapes(&self) -> bool {
+        self.track_escapes
+    }
+    /// Set whether `tick` counts the cells lost off the edges for
+    /// `escaped_population`, which costs a pass around the edges every tick
+    pub fn set_track_escapes(&mut self, track_escapes: bool) {
+        self.track_escapes = track_escapes;
+    }
+    /// Whether the last tick left every cell unchanged
+    ///
+    /// Always false before the first tick
+    pub fn is_stable(&self) -> bool {
+        self.generation > 0 && self.last_changed == 0
+    }
+    /// Step back to the state before the last tick
+    ///
+    /// Only the live cells are restored, not their ages or dying states,
+    /// and the counts of the last tick go back to zero, as after `reset`.
+    /// Returns false when there is no earlier state to go back to
+    pub fn undo(&mut self) -> bool {
+        match self.history.pop_back() {
+            Some(mut cells) => {
+                std::mem::swap(&mut self.cells, &mut cells);
+                self.clear_walls();
+                self.forget_changed(&cells);
+                self.generation = self.generation.saturating_sub(1);
+                self.changed.clear();
+                self.flipped.clear();
+                self.last_changed = 0;
+                self.births = 0;
+                self.deaths = 0;
+                self.escaped = 0;
+                true
+            }
+            None => false,
+        }
+    }
+    /// Set how many past generations `undo` can step back through
+    ///
+    /// Zero turns the history off
+    pub fn set_history_limit(&mut self, limit: u32) {
+        self.history_limit = limit as usize;
+        while self.history.len() > self.history_limit {
+            self.history.pop_front();
+        }
+    }
+    /// Hash of the dimensions and cells, stable across runs and platforms
+    ///
+    /// The same value as `fingerprint`
+    pub fn state_hash(&self) -> u64 {
+        self.fingerprint()
+    }
+    /// FNV-1a hash of the dimensions and packed cell words, stable across
+    /// runs and platforms
+    ///
+    /// Only the live cells and dimensions count, so universes with different
+    /// rules or generations can share a fingerprint
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = Fnv1a::new();
+        hasher.write_u32(self.width);
+        hasher.write_u32(self.height);
+        for &word in self.cells.as_slice() {
+            hasher.write_u32(word);
+        }
+        hasher.finish()
+    }
+    /// Find the period of the cycle the universe ends up in within
+    /// `max_steps` ticks, if any
+    ///
+    /// The universe is restored to its current state afterwards
+    pub fn detect_period(&mut self, max_steps: u32) -> Option<u32> {
+        // The history is set aside rather than copied, it is put back as is
+        let history = std::mem::take(&mut self.history);
+        let saved = self.clone();
+        self.paused = false;
+
+        let mut seen = HashMap::new();
+        seen.insert(self.fingerprint(), 0);
+        let mut period = None;
+        for step in 1..=max_steps {
+            self.tick();
+            if let Some(first_seen) = seen.insert(self.fingerprint(), step) {
+                period = Some(step - first_seen);
+                break;
+            }
+        }
+
+        *self = saved;
+        self.history = history;
+        period
+    }
+    /// Find the period after which the live cells come back to their
+    /// current shape, possibly moved elsewhere like a spaceship
+    ///
+    /// A still life has period 1. Only the first 256 ticks are searched,
+    /// and patterns that turn into something else before settling give
+    /// `None`. The ticks are run on a copy, leaving the universe untouched.
+    pub fn period_at(&mut self) -> Option<u32> {
+        // The history is set aside so it isn't copied along
+        let history = std::mem::take(&mut self.history);
+        let mut probe = self.clone();
+        self.history = history;
+        probe.history_limit = 0;
+        probe.paused = false;
+
+        let shape = self.live_shape();
+        (1..=MAX_PERIOD).find(|_| {
+            probe.tick();
+            probe.live_shape() == shape
+        })
     }
     pub fn width(&self) -> u32 {
         self.width
@@ -229,9 +1757,480 @@ impl Universe {
     pub fn height(&self) -> u32 {
         self.height
     }
+    /// Number of ticks since the universe was created, or since `reset`,
+    /// `set_width` or `set_height` started it over
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+    /// Number of live cells
+    pub fn population(&self) -> u32 {
+        self.cells.count_ones(..) as u32
+    }
+    /// Generation, population and the counts of the last tick in one call
+    pub fn stats(&self) -> Stats {
+        Stats {
+            generation: self.generation,
+            population: self.population(),
+            births: self.births,
+            deaths: self.deaths,
+            changed: self.last_changed,
+        }
+    }
+    /// Whether every cell is dead, checked a word at a time
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_clear()
+    }
+    /// Number of live cells in the `w` by `h` rectangle whose top-left cell
+    /// is at `row`, `col`
+    ///
+    /// The rectangle is clipped to the universe rather than wrapped
+    pub fn population_in_rect(&self, row: u32, col: u32, w: u32, h: u32) -> u32 {
+        if row >= self.height || col >= self.width {
+            return 0;
+        }
+        let last_row = row.saturating_add(h).min(self.height);
+        let last_col = col.saturating_add(w).min(self.width);
+        (row..last_row)
+            .map(|r| {
+                let start = self.get_index(r, col);
+                self.cells
+                    .count_ones(start..start + (last_col - col) as usize) as u32
+            })
+            .sum()
+    }
+    /// Number of separate groups of live cells, where cells touching
+    /// through a side or a corner belong to the same group
+    ///
+    /// Groups connect across the edges of a toroidal universe
+    pub fn component_count(&self) -> u32 {
+        self.cluster_sizes().len() as u32
+    }
+    /// Number of cells in the biggest group of live cells, see
+    /// `component_count`
+    pub fn largest_cluster(&self) -> u32 {
+        self.cluster_sizes().into_iter().max().unwrap_or(0)
+    }
+    /// Fraction of the cells that are alive, from 0 to 1
+    pub fn density(&self) -> f64 {
+        match self.cells.len() {
+            0 => 0.0,
+            size => self.population() as f64 / size as f64,
+        }
+    }
+    pub fn boundary_mode(&self) -> BoundaryMode {
+        self.boundary_mode
+    }
+    /// Set how the universe treats cells beyond its edges
+    pub fn set_boundary_mode(&mut self, mode: BoundaryMode) {
+        self.boundary_mode = mode;
+    }
+    /// Set the rule used by `tick` from a string like `"B36/S23"`, see
+    /// `Rule::parse`
+    ///
+    /// An invalid rule leaves the current one in place
+    pub fn set_rule_str(&mut self, rule: &str) -> Result<(), String> {
+        self.rule = Rule::parse(rule)?;
+        Ok(())
+    }
+    pub fn insert_mode(&self) -> InsertMode {
+        self.insert_mode
+    }
+    /// Set whether `insert_pattern` and `insert_glider` wrap cells beyond
+    /// the edges around or drop them
+    pub fn set_insert_mode(&mut self, mode: InsertMode) {
+        self.insert_mode = mode;
+    }
+    pub fn neighborhood(&self) -> Neighborhood {
+        self.neighborhood
+    }
+    /// Set which surrounding cells count as neighbors
+    pub fn set_neighborhood(&mut self, neighborhood: Neighborhood) {
+        self.neighborhood = neighborhood;
+    }
+    /// Pointer to the cells, packed 32 to a `u32` word in row-major order
+    ///
+    /// The pointer dangles as soon as the cells are reallocated, which
+    /// happens whenever the universe is resized, so read through it right
+    /// away or use `cells_bytes`
     pub fn cells(&self) -> *const u32 {
         self.cells.as_slice().as_ptr()
     }
+    /// Copy of the packed cells that `cells` points to, as little endian
+    /// bytes
+    pub fn cells_bytes(&self) -> Vec<u8> {
+        self.cells
+            .as_slice()
+            .iter()
+            .flat_map(|word| word.to_le_bytes())
+            .collect()
+    }
+    /// The cells of a single row, packed eight to a byte with the first
+    /// column in the lowest bit
+    ///
+    /// Panics if the row is beyond the bottom edge
+    pub fn row_bits(&self, row: u32) -> Vec<u8> {
+        assert!(
+            row < self.height,
+            "row {} is outside the {}x{} universe",
+            row,
+            self.width,
+            self.height
+        );
+        let mut bytes = vec![0; self.width.div_ceil(8) as usize];
+        let first = self.get_index(row, 0);
+        for col in 0..self.width as usize {
+            if self.cells[first + col] {
+                bytes[col / 8] |= 1 << (col % 8);
+            }
+        }
+        bytes
+    }
+    /// Make each cell alive with the given probability, keeping the
+    /// dimensions and generation
+    ///
+    /// `probability` is clamped to `[0, 1]`, and the same seed always yields
+    /// the same board. The new board is what `reset` goes back to.
+    pub fn randomize(&mut self, probability: f64, seed: u64) {
+        let probability = probability.clamp(0.0, 1.0);
+        let mut rng = SplitMix64::new(seed);
+        for i in 0..self.cells.len() {
+            self.cells.set(i, rng.next_f64() < probability);
+        }
+        self.clear_walls();
+        self.ages.fill(0);
+        self.states.fill(0);
+        self.initial = Some(self.cells.clone());
+    }
+    /// Go back to the starting cells and generation 0, forgetting the
+    /// history, ages, dying states and the counts of the last tick
+    ///
+    /// The starting cells are those `new`, `new_with_seed_pattern`,
+    /// `random` or the last `randomize` left, or else the cells at the first
+    /// tick since the universe was created or resized. Walls and rules stay
+    /// as they are.
+    pub fn reset(&mut self) {
+        if let Some(initial) = &self.initial {
+            self.cells.clone_from(initial);
+            self.clear_walls();
+        }
+        self.generation = 0;
+        self.changed.clear();
+        self.flipped.clear();
+        self.history.clear();
+        self.ages.fill(0);
+        self.states.fill(0);
+        self.last_changed = 0;
+        self.births = 0;
+        self.deaths = 0;
+        self.escaped = 0;
+    }
+    /// Kill every cell, keeping the dimensions and generation
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.ages.fill(0);
+        self.states.fill(0);
+    }
+    /// Pointer to the age of every cell, one `u32` per cell in row-major
+    /// order
+    ///
+    /// A cell's age is the number of ticks it has been alive for in a row:
+    /// 1 when born, growing by one for every tick it survives, and 0 while
+    /// dead. Resizing resets every age to 0.
+    pub fn ages(&self) -> *const u32 {
+        self.ages.as_ptr()
+    }
+    /// One byte per cell in row-major order, 0 for dead and 255 for alive,
+    /// ready to be used as a grayscale image
+    pub fn to_grayscale(&self) -> Vec<u8> {
+        (0..self.cells.len())
+            .map(|idx| if self.cells[idx] { 255 } else { 0 })
+            .collect()
+    }
+    /// Set every cell from one byte per cell in row-major order, where any
+    /// nonzero byte is alive, e.g. from `to_grayscale`
+    ///
+    /// Panics unless there are exactly `width * height` bytes
+    pub fn set_all_cells(&mut self, data: &[u8]) {
+        assert_eq!(
+            data.len(),
+            self.cells.len(),
+            "expected one byte for each cell of the {}x{} universe",
+            self.width,
+            self.height
+        );
+        for (idx, &byte) in data.iter().enumerate() {
+            self.write_cell(idx, byte != 0);
+        }
+    }
+    /// Stamp a glider heading south-east with its top-left at the given cell
+    ///
+    /// The glider's 3x3 box overwrites whatever was there, and wraps around
+    /// the edges or is clipped depending on `set_insert_mode`
+    pub fn insert_glider(&mut self, row: u32, column: u32) {
+        const GLIDER: [[bool; 3]; 3] = [
+            [false, true, false],
+            [false, false, true],
+            [true, true, true],
+        ];
+        for (dr, line) in GLIDER.iter().enumerate() {
+            for (dc, &alive) in line.iter().enumerate() {
+                if let Some(idx) =
+                    self.insert_index(row as u64 + dr as u64, column as u64 + dc as u64)
+                {
+                    self.write_cell(idx, alive);
+                }
+            }
+        }
+    }
+    /// Get the state of a single cell
+    ///
+    /// Coordinates beyond the edges wrap around, the same way neighbors
+    /// do on a toroidal universe
+    pub fn get_cell(&self, row: u32, column: u32) -> Cell {
+        Cell::from(self.cells[self.get_wrapped_index(row as u64, column as u64)])
+    }
+    /// Get the state of a single cell: 0 when dead, 1 when alive, and 2
+    /// onwards while dying under a Generations rule or Brian's Brain
+    ///
+    /// Coordinates beyond the edges wrap around like in `get_cell`
+    pub fn cell_state(&self, row: u32, column: u32) -> u8 {
+        self.state_at(self.get_wrapped_index(row as u64, column as u64))
+    }
+    /// Set the state of a single cell, numbered as in `cell_state`
+    ///
+    /// States the rule family does not have, like any dying state under a
+    /// life-like rule, make the cell dead
+    pub fn set_cell_state(&mut self, row: u32, column: u32, state: u8) {
+        let idx = self.get_index(row, column);
+        let state = if state < self.state_count() && !self.walls[idx] {
+            state
+        } else {
+            0
+        };
+        self.write_cell(idx, state == 1);
+        self.states[idx] = state;
+    }
+    /// Force a cell alive or dead, whatever its current state
+    pub fn set_cell(&mut self, row: u32, column: u32, alive: bool) {
+        let idx = self.get_index(row, column);
+        self.write_cell(idx, alive);
+    }
+    /// Flip every cell, a word at a time
+    pub fn invert(&mut self) {
+        self.cells.toggle_range(..);
+        self.clear_walls();
+        self.ages.fill(0);
+        self.states.fill(0);
+    }
+    /// Move every live cell down by `dr` rows and right by `dc` columns,
+    /// wrapping around the edges
+    pub fn translate(&mut self, dr: i32, dc: i32) {
+        self.remap(|universe, row, col| {
+            universe.wrap(row as i64 + dr as i64, col as i64 + dc as i64)
+        });
+    }
+    /// Mirror the universe left to right
+    pub fn flip_horizontal(&mut self) {
+        self.remap(|universe, row, col| (row, universe.width - 1 - col));
+    }
+    /// Mirror the universe top to bottom
+    pub fn flip_vertical(&mut self) {
+        self.remap(|universe, row, col| (universe.height - 1 - row, col));
+    }
+    /// Swap rows and columns, so cell `(row, col)` moves to `(col, row)`
+    /// and the universe becomes `height` cells wide and `width` cells tall
+    ///
+    /// Like `resize`, this forgets the history, ages, dying states and walls
+    pub fn transpose(&mut self) {
+        let mut cells = FixedBitSet::with_capacity(self.cells.len());
+        for (row, col) in self.live_cells_iter() {
+            cells.insert((col * self.height + row) as usize);
+        }
+        std::mem::swap(&mut self.width, &mut self.height);
+        self.cells = cells;
+        self.reset_cell_buffers();
+    }
+    /// Rotate the universe a quarter turn, clockwise or counterclockwise,
+    /// so the universe becomes `height` cells wide and `width` cells tall
+    ///
+    /// Like `transpose`, this forgets the history, ages, dying states and
+    /// walls
+    pub fn rotate90(&mut self, clockwise: bool) {
+        self.transpose();
+        if clockwise {
+            self.flip_horizontal();
+        } else {
+            self.flip_vertical();
+        }
+    }
+    /// Copy the `width` x `height` region with its top-left at the given
+    /// cell into a new universe with the same rules
+    ///
+    /// The region is clamped to the universe's edges
+    pub fn subgrid(&self, row: u32, column: u32, width: u32, height: u32) -> Universe {
+        let row = row.min(self.height);
+        let column = column.min(self.width);
+        let width = width.min(self.width - column);
+        let height = height.min(self.height - row);
+        let mut universe = self.with_same_rules(width, height);
+        for r in 0..universe.height.min(height) {
+            for c in 0..universe.width.min(width) {
+                let idx = universe.get_index(r, c);
+                universe
+                    .cells
+                    .set(idx, self.cells[self.get_index(row + r, column + c)]);
+            }
+        }
+        universe
+    }
+    /// Shrink the universe to the bounding box of its live cells
+    ///
+    /// An empty universe is left as it is
+    pub fn crop_to_live(&mut self) {
+        if let Some((min_row, min_col, max_row, max_col)) = self.live_bounds() {
+            let cropped = self.subgrid(
+                min_row,
+                min_col,
+                max_col - min_col + 1,
+                max_row - min_row + 1,
+            );
+            self.width = cropped.width;
+            self.height = cropped.height;
+            self.cells = cropped.cells;
+            self.reset_cell_buffers();
+        }
+    }
+    /// Combine another universe's cells into this one with a bitwise
+    /// operation
+    ///
+    /// Panics if the universes have different dimensions
+    pub fn combine(&mut self, other: &Universe, op: BitOp) {
+        assert!(
+            self.width == other.width && self.height == other.height,
+            "cannot combine a {}x{} universe with a {}x{} one",
+            self.width,
+            self.height,
+            other.width,
+            other.height
+        );
+        let before = self.cells.clone();
+        match op {
+            BitOp::And => self.cells.intersect_with(&other.cells),
+            BitOp::Or => self.cells.union_with(&other.cells),
+            BitOp::Xor => self.cells.symmetric_difference_with(&other.cells),
+        }
+        self.clear_walls();
+        self.forget_changed(&before);
+    }
+    /// Blend another universe's cells into this one with a bitwise
+    /// operation, with the other universe's top-left at the given cell
+    ///
+    /// Only the covered cells change, and the parts of `other` beyond the
+    /// edges are clipped
+    pub fn stamp(&mut self, other: &Universe, row: u32, column: u32, op: BitOp) {
+        let rows = other.height.min(self.height.saturating_sub(row));
+        let columns = other.width.min(self.width.saturating_sub(column));
+        for r in 0..rows {
+            for c in 0..columns {
+                let stamped = other.cells[other.get_index(r, c)];
+                let idx = self.get_index(row + r, column + c);
+                let alive = match op {
+                    BitOp::And => self.cells[idx] && stamped,
+                    BitOp::Or => self.cells[idx] || stamped,
+                    BitOp::Xor => self.cells[idx] != stamped,
+                };
+                self.write_cell(idx, alive);
+            }
+        }
+    }
+    /// Set every cell of the `width` x `height` rectangle with its top-left
+    /// at the given cell alive or dead
+    ///
+    /// The rectangle is clamped to the universe's edges
+    pub fn fill_rect(&mut self, row: u32, column: u32, width: u32, height: u32, alive: bool) {
+        let end_row = row.saturating_add(height).min(self.height);
+        let end_col = column.saturating_add(width).min(self.width);
+        if column >= end_col {
+            return;
+        }
+        for r in row..end_row {
+            let start = self.get_index(r, column);
+            for idx in start..start + (end_col - column) as usize {
+                self.write_cell(idx, alive);
+            }
+        }
+    }
+    /// Set every cell along the segment from `(r0, c0)` to `(r1, c1)` alive
+    /// or dead, using Bresenham's line algorithm
+    ///
+    /// Points beyond the universe's edges are skipped
+    pub fn draw_line(&mut self, r0: u32, c0: u32, r1: u32, c1: u32, alive: bool) {
+        let (mut row, mut col) = (r0 as i64, c0 as i64);
+        let (end_row, end_col) = (r1 as i64, c1 as i64);
+        let d_row = -(end_row - row).abs();
+        let d_col = (end_col - col).abs();
+        let step_row = if row < end_row { 1 } else { -1 };
+        let step_col = if col < end_col { 1 } else { -1 };
+        let mut error = d_col + d_row;
+        loop {
+            if row < self.height as i64 && col < self.width as i64 {
+                self.set_cell(row as u32, col as u32, alive);
+            }
+            if row == end_row && col == end_col {
+                break;
+            }
+            let doubled = 2 * error;
+            if doubled >= d_row {
+                error += d_row;
+                col += step_col;
+            }
+            if doubled <= d_col {
+                error += d_col;
+                row += step_row;
+            }
+        }
+    }
+    /// Turn a cell into an immovable wall, or back into a normal cell
+    ///
+    /// A wall cell is dead and never changes. It counts as a dead neighbor
+    /// unless `set_walls_alive` says otherwise. Changing the dimensions
+    /// removes every wall.
+    pub fn set_wall(&mut self, row: u32, column: u32, is_wall: bool) {
+        let idx = self.get_index(row, column);
+        self.walls.set(idx, is_wall);
+        if is_wall {
+            self.cells.set(idx, false);
+        }
+    }
+    pub fn is_wall(&self, row: u32, column: u32) -> bool {
+        self.walls[self.get_index(row, column)]
+    }
+    /// Set whether walls count as live neighbors
+    pub fn set_walls_alive(&mut self, walls_alive: bool) {
+        self.walls_alive = walls_alive;
+    }
+    pub fn auto_expand(&self) -> bool {
+        self.auto_expand
+    }
+    /// Grow the universe before every tick in which live cells lie on its
+    /// edges, by 8 rows or columns beyond each such edge
+    ///
+    /// The live cells move along with the new top-left corner, so patterns
+    /// never wrap around or hit the edge. Walls, ages, dying states and the
+    /// history move along too.
+    pub fn set_auto_expand(&mut self, auto_expand: bool) {
+        self.auto_expand = auto_expand;
+    }
+    pub fn paused(&self) -> bool {
+        self.paused
+    }
+    /// Pause or resume the simulation
+    ///
+    /// While paused `tick` leaves the cells and generation alone, and the
+    /// cells can still be edited
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
     /// Toggle cell state
     ///
     /// Alive cell -> Dead cell
@@ -239,24 +2238,30 @@ impl Universe {
     /// Dead cell -> Alive cell
     pub fn toggle_cell(&mut self, row: u32, column: u32) {
         let idx = self.get_index(row, column);
-        self.cells.set(idx, !self.cells[idx]);
+        self.write_cell(idx, !self.cells[idx]);
     }
 }
 
-use std::fmt;
+/// Universes are equal when they have the same dimensions, cells and
+/// generation
+impl PartialEq for Universe {
+    fn eq(&self, other: &Self) -> bool {
+        self.width == other.width
+            && self.height == other.height
+            && self.cells == other.cells
+            && self.generation == other.generation
+    }
+}
+
+impl Default for Universe {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl fmt::Display for Universe {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        // This will create 2d vector that contains 1d vector slice
-        for line in self.cells.as_slice().chunks(self.width as usize) {
-            for &cell in line {
-                let symbol = if cell == 0 { '◻' } else { '◼' };
-                write!(f, "{}", symbol)?;
-            }
-            write!(f, "\n")?;
-        }
-
-        Ok(())
+        f.write_str(&self.render_with('◻', '◼'))
     }
 }
 
@@ -266,12 +2271,8 @@ impl fmt::Display for Universe {
 #[global_allocator]
 static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 
+/// The version of this crate, as in `Cargo.toml`
 #[wasm_bindgen]
-extern "C" {
-    fn alert(s: &str);
-}
-
-#[wasm_bindgen]
-pub fn greet(name: &str) {
-    alert(&format!("Hello, {}!", name));
+pub fn version() -> String {
+    env!("CARGO_PKG_VERSION").to_string()
 }
@@ -0,0 +1,86 @@
+//! Helpers for patterns given as lists of `(row, col)` offsets, as taken by
+//! `Universe::insert_pattern`
+
+/// Rotate a pattern clockwise by the given number of quarter turns
+///
+/// The rotation happens within the pattern's bounding box, so the result
+/// again starts at row 0 and column 0
+pub fn rotate_pattern(cells: &[(u32, u32)], quarter_turns: u8) -> Vec<(u32, u32)> {
+    let min_row = cells.iter().map(|&(row, _)| row).min().unwrap_or(0);
+    let min_col = cells.iter().map(|&(_, col)| col).min().unwrap_or(0);
+    let mut rotated: Vec<(u32, u32)> = cells
+        .iter()
+        .map(|&(row, col)| (row - min_row, col - min_col))
+        .collect();
+    for _ in 0..quarter_turns % 4 {
+        let max_row = rotated.iter().map(|&(row, _)| row).max().unwrap_or(0);
+        for cell in rotated.iter_mut() {
+            *cell = (cell.1, max_row - cell.0);
+        }
+    }
+    rotated
+}
+
+/// Drawings of the named patterns, one string per row with `O` for a live
+/// cell and `.` for a dead one
+const NAMED_PATTERNS: [(&str, &[&str]); 7] = [
+    ("glider", &[".O.", "..O", "OOO"]),
+    ("blinker", &["OOO"]),
+    ("toad", &[".OOO", "OOO."]),
+    ("beacon", &["OO..", "OO..", "..OO", "..OO"]),
+    (
+        "pulsar",
+        &[
+            "..OOO...OOO..",
+            ".............",
+            "O....O.O....O",
+            "O....O.O....O",
+            "O....O.O....O",
+            "..OOO...OOO..",
+            ".............",
+            "..OOO...OOO..",
+            "O....O.O....O",
+            "O....O.O....O",
+            "O....O.O....O",
+            ".............",
+            "..OOO...OOO..",
+        ],
+    ),
+    ("lwss", &[".O..O", "O....", "O...O", "OOOO."]),
+    (
+        "gosper_glider_gun",
+        &[
+            "........................O...........",
+            "......................O.O...........",
+            "............OO......OO............OO",
+            "...........O...O....OO............OO",
+            "OO........O.....O...OO..............",
+            "OO........O...O.OO....O.O...........",
+            "..........O.....O.......O...........",
+            "...........O...O....................",
+            "............OO......................",
+        ],
+    ),
+];
+
+/// Look up a well-known pattern by name, ignoring case
+///
+/// Known names are `glider`, `blinker`, `toad`, `beacon`, `pulsar`, `lwss`
+/// (the lightweight spaceship) and `gosper_glider_gun`. The offsets start
+/// at row 0 and column 0.
+pub fn pattern_by_name(name: &str) -> Option<Vec<(u32, u32)>> {
+    let (_, rows) = NAMED_PATTERNS
+        .iter()
+        .find(|(known, _)| known.eq_ignore_ascii_case(name))?;
+    Some(
+        rows.iter()
+            .enumerate()
+            .flat_map(|(row, line)| {
+                line.chars()
+                    .enumerate()
+                    .filter(|&(_, ch)| ch == 'O')
+                    .map(move |(col, _)| (row as u32, col as u32))
+            })
+            .collect(),
+    )
+}
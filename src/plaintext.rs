@@ -0,0 +1,67 @@
+//! Import of patterns in the plaintext (`.cells`) format
+//!
+//! Every line is a row of the pattern, where `.` is a dead cell and `O` is a
+//! live cell. Lines starting with `!` are comments.
+
+use crate::Universe;
+
+impl Universe {
+    /// Parse a plaintext pattern into a universe just big enough to hold it
+    ///
+    /// The width is that of the longest row and short rows are padded with
+    /// dead cells. Whitespace is ignored.
+    pub fn from_plaintext(s: &str) -> Result<Universe, String> {
+        let mut rows = Vec::new();
+        for line in s.lines().filter(|line| !line.starts_with('!')) {
+            let mut row = Vec::new();
+            for c in line.chars().filter(|c| !c.is_whitespace()) {
+                match c {
+                    '.' => row.push(false),
+                    'O' => row.push(true),
+                    c => return Err(format!("invalid character {:?} in plaintext pattern", c)),
+                }
+            }
+            rows.push(row);
+        }
+        while rows.last().is_some_and(Vec::is_empty) {
+            rows.pop();
+        }
+
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0) as u32;
+        let height = rows.len() as u32;
+        if width == 0 {
+            return Err("plaintext pattern has no cells".to_string());
+        }
+
+        let mut universe = Universe::empty(width, height);
+        for (row, cells) in rows.iter().enumerate() {
+            for (col, &alive) in cells.iter().enumerate() {
+                let idx = universe.get_index(row as u32, col as u32);
+                universe.cells.set(idx, alive);
+            }
+        }
+        Ok(universe)
+    }
+    /// Build a universe from a block of text, one line per row, where every
+    /// `alive` character is a live cell and any other character is dead
+    ///
+    /// Unlike `from_plaintext` nothing is rejected or skipped, so spaces are
+    /// dead cells too. The universe is as wide as the longest line and at
+    /// least 1x1.
+    pub fn from_grid_str(s: &str, alive: char) -> Universe {
+        let width = s
+            .lines()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+        let height = s.lines().count();
+        let mut universe = Universe::new_with_size(width as u32, height as u32);
+        for (row, line) in s.lines().enumerate() {
+            for (col, c) in line.chars().enumerate() {
+                let idx = universe.get_index(row as u32, col as u32);
+                universe.cells.set(idx, c == alive);
+            }
+        }
+        universe
+    }
+}
@@ -0,0 +1,185 @@
+//! Import and export of patterns in the run length encoded (RLE) format
+//!
+//! An RLE pattern starts with a header line like `x = 3, y = 3, rule = B3/S23`
+//! followed by the cells row by row, where `b` is a dead cell, `o` is a live
+//! cell, `$` ends a row and `!` ends the pattern. Each of them may be
+//! prefixed with a run count, e.g. `3o` for three live cells in a row.
+//! Lines starting with `#` are comments.
+
+use crate::{Rule, Universe, MAX_IMPORT_CELLS};
+
+/// Keep exported lines below the customary 70 characters
+const MAX_LINE_LENGTH: usize = 70;
+
+impl Universe {
+    /// Parse an RLE pattern into a universe sized to its `x`/`y` header, at
+    /// least 1x1
+    ///
+    /// The pattern is placed at the top-left and every other cell is dead.
+    /// A `rule` in the header replaces the default rule.
+    pub fn from_rle(s: &str) -> Result<Universe, String> {
+        let mut lines = s
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'));
+
+        let header = lines.next().ok_or("RLE pattern is empty")?;
+        let (width, height, rule) = parse_header(header)?;
+        if (width as u64) * (height as u64) > MAX_IMPORT_CELLS {
+            return Err(format!(
+                "universe size {}x{} is over the limit of {} cells",
+                width, height, MAX_IMPORT_CELLS
+            ));
+        }
+        let mut universe = Universe::empty(width, height);
+        if let Some(rule) = rule {
+            universe.set_rule(rule);
+        }
+
+        let mut row: u32 = 0;
+        let mut col: u32 = 0;
+        let mut run: Option<u32> = None;
+        for c in lines.flat_map(str::chars) {
+            match c {
+                '0'..='9' => {
+                    let digit = c.to_digit(10).unwrap();
+                    let count = run
+                        .unwrap_or(0)
+                        .checked_mul(10)
+                        .and_then(|n| n.checked_add(digit));
+                    run = Some(count.ok_or("RLE run count is too large")?);
+                }
+                'b' | 'o' => {
+                    let count = run.take().unwrap_or(1);
+                    let end = col.checked_add(count).filter(|&end| end <= width);
+                    if row >= height || end.is_none() {
+                        return Err(format!(
+                            "RLE pattern exceeds its {}x{} header at row {}",
+                            width, height, row
+                        ));
+                    }
+                    if c == 'o' {
+                        for offset in 0..count {
+                            let idx = universe.get_index(row, col + offset);
+                            universe.cells.set(idx, true);
+                        }
+                    }
+                    col += count;
+                }
+                '$' => {
+                    row = row
+                        .checked_add(run.take().unwrap_or(1))
+                        .ok_or("RLE row count is too large")?;
+                    col = 0;
+                }
+                '!' => return Ok(universe),
+                c if c.is_whitespace() => {}
+                c => return Err(format!("invalid character {:?} in RLE pattern", c)),
+            }
+        }
+
+        Err("RLE pattern is missing the `!` terminator".to_string())
+    }
+    /// Encode the live cells as an RLE pattern
+    ///
+    /// The pattern is cropped to the bounding box of the live cells, so an
+    /// empty universe becomes `x = 0, y = 0` with an empty body.
+    pub fn to_rle(&self) -> String {
+        let bounds = self.live_bounds();
+        let header = match bounds {
+            Some((min_row, min_col, max_row, max_col)) => format!(
+                "x = {}, y = {}, rule = {}",
+                max_col - min_col + 1,
+                max_row - min_row + 1,
+                self.rule
+            ),
+            None => format!("x = 0, y = 0, rule = {}", self.rule),
+        };
+
+        // Collect the body as (count, tag) runs, dropping dead runs at the
+        // end of a row and merging consecutive row ends
+        let mut runs: Vec<(u32, char)> = Vec::new();
+        if let Some((min_row, min_col, max_row, max_col)) = bounds {
+            for row in min_row..=max_row {
+                if row > min_row {
+                    push_run(&mut runs, 1, '$');
+                }
+                let mut dead = 0;
+                for col in min_col..=max_col {
+                    if self.cells[self.get_index(row, col)] {
+                        if dead > 0 {
+                            push_run(&mut runs, dead, 'b');
+                            dead = 0;
+                        }
+                        push_run(&mut runs, 1, 'o');
+                    } else {
+                        dead += 1;
+                    }
+                }
+            }
+        }
+        runs.push((1, '!'));
+
+        let mut body = String::new();
+        let mut line_length = 0;
+        for (count, tag) in runs {
+            let token = if count == 1 {
+                tag.to_string()
+            } else {
+                format!("{}{}", count, tag)
+            };
+            if line_length + token.len() > MAX_LINE_LENGTH {
+                body.push('\n');
+                line_length = 0;
+            }
+            line_length += token.len();
+            body.push_str(&token);
+        }
+
+        format!("{}\n{}\n", header, body)
+    }
+}
+
+/// Parse the `x = .., y = .., rule = ..` header line
+fn parse_header(header: &str) -> Result<(u32, u32, Option<Rule>), String> {
+    let mut width = None;
+    let mut height = None;
+    let mut rule = None;
+    for field in header.split(',') {
+        let mut parts = field.splitn(2, '=');
+        let key = parts.next().unwrap_or("").trim();
+        let value = parts
+            .next()
+            .ok_or_else(|| format!("malformed RLE header field {:?}", field))?
+            .trim();
+        match key {
+            "x" | "y" => {
+                // A zero size is bumped up to 1 like `new_with_size` does,
+                // which is what `to_rle` writes for an empty universe
+                let size = value
+                    .parse::<u32>()
+                    .map_err(|_| format!("invalid RLE size {:?}", value))?
+                    .max(1);
+                if key == "x" {
+                    width = Some(size);
+                } else {
+                    height = Some(size);
+                }
+            }
+            "rule" => rule = Some(Rule::parse(value)?),
+            _ => return Err(format!("unknown RLE header field {:?}", key)),
+        }
+    }
+    match (width, height) {
+        (Some(width), Some(height)) => Ok((width, height, rule)),
+        _ => Err(format!("RLE header {:?} needs both x and y", header)),
+    }
+}
+
+/// Append a run, merging it into the previous one when the tags match
+fn push_run(runs: &mut Vec<(u32, char)>, count: u32, tag: char) {
+    match runs.last_mut() {
+        Some((last_count, last_tag)) if *last_tag == tag => *last_count += count,
+        _ => runs.push((count, tag)),
+    }
+}
@@ -0,0 +1,137 @@
+//! Wireworld, a four-state automaton modelling electrons flowing along wires
+
+use crate::{cell_count, distinct_lines};
+use wasm_bindgen::prelude::*;
+
+/// The state of a Wireworld cell
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WireworldCell {
+    /// Never changes
+    Empty = 0,
+    /// Becomes an electron head when one or two neighbors are heads
+    Conductor = 1,
+    /// Becomes an electron tail
+    ElectronHead = 2,
+    /// Becomes a conductor again
+    ElectronTail = 3,
+}
+
+/// A toroidal Wireworld grid, one byte per cell in row-major order
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct WireworldUniverse {
+    width: u32,
+    height: u32,
+    generation: u64,
+    cells: Vec<WireworldCell>,
+    // Buffer for the next generation, swapped with `cells` on every tick
+    next: Vec<WireworldCell>,
+}
+
+impl WireworldUniverse {
+    fn get_index(&self, row: u32, column: u32) -> usize {
+        (row * self.width + column) as usize
+    }
+    /// Number of electron heads among the eight surrounding cells, with the
+    /// edges wrapping around
+    ///
+    /// On a grid only one or two cells across, wrapping lands on the same
+    /// row or column from several directions, which only counts once, as in
+    /// `Universe::live_neighbor_count`
+    fn head_neighbor_count(&self, row: u32, column: u32) -> u8 {
+        let rows = distinct_lines(
+            Some((row + self.height - 1) % self.height),
+            row,
+            Some((row + 1) % self.height),
+        );
+        let columns = distinct_lines(
+            Some((column + self.width - 1) % self.width),
+            column,
+            Some((column + 1) % self.width),
+        );
+        let mut count = 0;
+        for (i, neighbor_row) in rows.iter().enumerate() {
+            for (j, neighbor_col) in columns.iter().enumerate() {
+                if i == 1 && j == 1 {
+                    continue;
+                }
+                if let (Some(r), Some(c)) = (neighbor_row, neighbor_col) {
+                    if self.cells[self.get_index(*r, *c)] == WireworldCell::ElectronHead {
+                        count += 1;
+                    }
+                }
+            }
+        }
+        count
+    }
+}
+
+#[wasm_bindgen]
+impl WireworldUniverse {
+    /// Create a grid of empty cells, at least 1x1
+    pub fn new(width: u32, height: u32) -> WireworldUniverse {
+        let (width, height) = (width.max(1), height.max(1));
+        let size = cell_count(width, height);
+        WireworldUniverse {
+            width,
+            height,
+            generation: 0,
+            cells: vec![WireworldCell::Empty; size],
+            next: vec![WireworldCell::Empty; size],
+        }
+    }
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+    /// Number of ticks since the grid was created
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+    /// Pointer to the state of every cell, one byte per cell in row-major
+    /// order
+    pub fn cells(&self) -> *const WireworldCell {
+        self.cells.as_ptr()
+    }
+    /// Get the state of a single cell
+    pub fn cell_state(&self, row: u32, column: u32) -> WireworldCell {
+        self.cells[self.get_index(row, column)]
+    }
+    /// Set the state of a single cell
+    pub fn set_cell_state(&mut self, row: u32, column: u32, state: WireworldCell) {
+        let idx = self.get_index(row, column);
+        self.cells[idx] = state;
+    }
+    pub fn tick(&mut self) {
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let idx = self.get_index(row, col);
+                self.next[idx] = match self.cells[idx] {
+                    WireworldCell::Empty => WireworldCell::Empty,
+                    WireworldCell::ElectronHead => WireworldCell::ElectronTail,
+                    WireworldCell::ElectronTail => WireworldCell::Conductor,
+                    WireworldCell::Conductor => match self.head_neighbor_count(row, col) {
+                        1 | 2 => WireworldCell::ElectronHead,
+                        _ => WireworldCell::Conductor,
+                    },
+                };
+            }
+        }
+        std::mem::swap(&mut self.cells, &mut self.next);
+        self.generation += 1;
+    }
+}
+
+/// Grids are equal when they have the same dimensions, cells and generation
+impl PartialEq for WireworldUniverse {
+    fn eq(&self, other: &Self) -> bool {
+        self.width == other.width
+            && self.height == other.height
+            && self.cells == other.cells
+            && self.generation == other.generation
+    }
+}
@@ -0,0 +1,89 @@
+//! Test suite for native targets, run with a plain `cargo test`
+
+// With the `profiling` feature the tick timers call into the browser
+// console, which does not exist here
+#![cfg(not(feature = "profiling"))]
+// Some of the tested methods only exist on native targets
+#![cfg(not(target_arch = "wasm32"))]
+
+extern crate wasm_game_of_life;
+use wasm_game_of_life::Universe;
+
+#[test]
+pub fn test_tick_without_profiling() {
+    let mut universe = Universe::new_with_size(6, 6);
+    universe.set_cells(&[(1, 2), (2, 3), (3, 1), (3, 2), (3, 3)]);
+    universe.tick();
+    assert_eq!(universe.generation(), 1);
+    assert_eq!(
+        universe.live_cells(),
+        vec![(2, 1), (2, 3), (3, 2), (3, 3), (4, 2)]
+    );
+}
+
+#[test]
+pub fn test_render_ansi() {
+    let mut universe = Universe::new_with_size(4, 2);
+    universe.set_cells(&[(0, 1), (0, 2), (1, 3)]);
+    assert_eq!(
+        universe.render_ansi(),
+        " \x1b[32m██\x1b[0m \x1b[0m\n   \x1b[32m█\x1b[0m\n"
+    );
+}
+
+#[test]
+pub fn test_tick_with() {
+    let mut universe = Universe::new_with_size(8, 8);
+    // A blinker and a block, far enough apart not to interact
+    universe.set_cells(&[(5, 3), (5, 4), (5, 5), (1, 1), (1, 2), (2, 1), (2, 2)]);
+    let mut populations = Vec::new();
+    for _ in 0..10 {
+        universe.tick_with(&mut |universe| populations.push(universe.population()));
+    }
+    assert_eq!(populations, vec![7; 10]);
+    assert_eq!(universe.generation(), 10);
+
+    let mut total = 0;
+    let mut generations = Vec::new();
+    let mut record = |universe: &Universe| {
+        total += universe.population();
+        generations.push(universe.generation());
+    };
+    universe.clear();
+    universe.set_cells(&[(5, 5)]);
+    universe.tick_with(&mut record);
+    universe.tick_with(&mut record);
+    assert_eq!(total, 0);
+    assert_eq!(generations, vec![11, 12]);
+}
+
+/// The live cells after one tick, worked out cell by cell from the rule
+#[cfg(feature = "rayon")]
+fn reference_tick(universe: &Universe) -> Vec<(u32, u32)> {
+    let rule = universe.rule();
+    let mut live = Vec::new();
+    for row in 0..universe.height() {
+        for col in 0..universe.width() {
+            let alive = universe.get_cell(row, col) == wasm_game_of_life::Cell::Alive;
+            let neighbors = universe.live_neighbor_count(row, col);
+            if rule.next_state(alive, neighbors) {
+                live.push((row, col));
+            }
+        }
+    }
+    live
+}
+
+// Threads are not available on wasm, so the parallel tick is only tested here
+#[cfg(feature = "rayon")]
+#[test]
+pub fn test_parallel_tick_matches_reference() {
+    for &(width, height, seed) in &[(7, 5, 4), (64, 64, 5), (129, 70, 6)] {
+        let mut universe = Universe::random(width, height, seed);
+        for _ in 0..4 {
+            let expected = reference_tick(&universe);
+            universe.tick();
+            assert_eq!(universe.live_cells(), expected);
+        }
+    }
+}
@@ -8,7 +8,11 @@ use wasm_bindgen_test::*;
 wasm_bindgen_test_configure!(run_in_browser);
 
 extern crate wasm_game_of_life;
-use wasm_game_of_life::Universe;
+use wasm_game_of_life::patterns::{pattern_by_name, rotate_pattern};
+use wasm_game_of_life::{
+    version, BitOp, BoundaryMode, Cell, InsertMode, Kernel, Neighborhood, ReversibleUniverse, Rule,
+    RuleFamily, SeedPattern, Stats, Universe, UniverseBuilder, WireworldCell, WireworldUniverse,
+};
 
 #[cfg(test)]
 pub fn input_spaceship() -> Universe {
@@ -41,3 +45,2372 @@ pub fn test_tick() {
     input_universe.tick();
     assert_eq!(&input_universe.get_cells(), &expected_universe.get_cells());
 }
+
+#[wasm_bindgen_test]
+pub fn test_render() {
+    let mut universe = Universe::new();
+    universe.set_width(4);
+    universe.set_height(4);
+    universe.set_cells(&[(0, 0), (1, 2), (2, 1), (3, 3)]);
+
+    // One symbol per cell, one line per row
+    assert_eq!(universe.render(), "◼◻◻◻\n◻◻◼◻\n◻◼◻◻\n◻◻◻◼\n");
+}
+
+#[cfg(test)]
+pub fn corner_universe() -> Universe {
+    let mut universe = Universe::new();
+    universe.set_width(4);
+    universe.set_height(4);
+    // Two neighbors of (0, 0) sit inside the board, two only across the edges
+    universe.set_cells(&[(0, 1), (1, 0), (0, 3), (3, 3)]);
+    universe
+}
+
+#[wasm_bindgen_test]
+pub fn test_toroidal_corner_neighbors() {
+    let universe = corner_universe();
+    assert_eq!(universe.boundary_mode(), BoundaryMode::Toroidal);
+    assert_eq!(universe.live_neighbor_count(0, 0), 4);
+}
+
+#[wasm_bindgen_test]
+pub fn test_dead_corner_neighbors() {
+    let mut universe = corner_universe();
+    universe.set_boundary_mode(BoundaryMode::Dead);
+    assert_eq!(universe.live_neighbor_count(0, 0), 2);
+}
+
+#[wasm_bindgen_test]
+pub fn test_parse_rule() {
+    let rule = Rule::parse("B36/S23").unwrap();
+    for n in 0..9 {
+        assert_eq!(rule.birth[n], n == 3 || n == 6);
+        assert_eq!(rule.survive[n], n == 2 || n == 3);
+    }
+    assert_eq!(rule.to_string(), "B36/S23");
+    assert_eq!(Rule::parse("B3/S23").unwrap(), Rule::default());
+    assert!(Rule::parse("B39/S23").is_err());
+    assert!(Rule::parse("B3").is_err());
+    assert!(Rule::parse("X3/S23").is_err());
+}
+
+#[cfg(test)]
+pub fn empty_universe(width: u32, height: u32) -> Universe {
+    Universe::new_with_size(width, height)
+}
+
+#[wasm_bindgen_test]
+pub fn test_highlife_replicator() {
+    let highlife = Rule::parse("B36/S23").unwrap();
+    let mut universe = empty_universe(16, 16);
+    universe.set_rule(highlife);
+    universe.set_cells(&[
+        (5, 7),
+        (5, 8),
+        (5, 9),
+        (6, 6),
+        (6, 9),
+        (7, 5),
+        (7, 9),
+        (8, 5),
+        (8, 8),
+        (9, 5),
+        (9, 6),
+        (9, 7),
+    ]);
+    universe.tick();
+    universe.tick();
+
+    // (7, 7) has six live neighbors here, and is only born under HighLife
+    let mut expected = empty_universe(16, 16);
+    expected.set_cells(&[
+        (4, 7),
+        (4, 8),
+        (4, 9),
+        (5, 6),
+        (5, 10),
+        (6, 5),
+        (6, 10),
+        (7, 4),
+        (7, 7),
+        (7, 10),
+        (8, 4),
+        (8, 9),
+        (9, 4),
+        (9, 8),
+        (10, 5),
+        (10, 6),
+        (10, 7),
+    ]);
+    assert_eq!(universe.get_cells(), expected.get_cells());
+}
+
+#[cfg(test)]
+pub fn live_coordinates(universe: &Universe) -> Vec<(u32, u32)> {
+    let width = universe.width();
+    universe
+        .get_cells()
+        .ones()
+        .map(|idx| (idx as u32 / width, idx as u32 % width))
+        .collect()
+}
+
+#[wasm_bindgen_test]
+pub fn test_glider_from_rle() {
+    let universe = Universe::from_rle(
+        "#N Glider
+x = 3, y = 3, rule = B3/S23
+bob$2bo$3o!",
+    )
+    .unwrap();
+    assert_eq!(universe.width(), 3);
+    assert_eq!(universe.height(), 3);
+    assert_eq!(
+        live_coordinates(&universe),
+        vec![(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]
+    );
+}
+
+#[wasm_bindgen_test]
+pub fn test_blinker_from_rle() {
+    let universe = Universe::from_rle("x = 5, y = 3\n$b3o!").unwrap();
+    assert_eq!(universe.width(), 5);
+    assert_eq!(universe.height(), 3);
+    assert_eq!(live_coordinates(&universe), vec![(1, 1), (1, 2), (1, 3)]);
+}
+
+#[wasm_bindgen_test]
+pub fn test_malformed_rle() {
+    assert!(Universe::from_rle("").is_err());
+    assert!(Universe::from_rle("x = 3\n3o!").is_err());
+    assert!(Universe::from_rle("x = three, y = 1\n3o!").is_err());
+    assert!(Universe::from_rle("x = 3, y = 1\n3o").is_err());
+    assert!(Universe::from_rle("x = 2, y = 1\n3o!").is_err());
+    assert!(Universe::from_rle("x = 3, y = 1\n3q!").is_err());
+    // Sizes and runs that overflow are errors rather than panics
+    assert!(Universe::from_rle("x = 100000, y = 100000\n!").is_err());
+    assert_eq!(
+        Universe::from_rle("x = 65535, y = 65535\n!"),
+        Err("universe size 65535x65535 is over the limit of 16777216 cells".to_string())
+    );
+    assert!(Universe::from_rle("x = 4096, y = 4096\n!").is_ok());
+    assert!(Universe::from_rle("x = 3, y = 1\n2o4294967295o!").is_err());
+    assert!(Universe::from_rle("x = 3, y = 2\n$4294967295$o!").is_err());
+}
+
+#[cfg(test)]
+pub fn assert_rle_round_trip(rle: &str) {
+    let universe = Universe::from_rle(rle).unwrap();
+    let round_trip = Universe::from_rle(&universe.to_rle()).unwrap();
+    assert_eq!(round_trip.width(), universe.width());
+    assert_eq!(round_trip.height(), universe.height());
+    assert_eq!(round_trip.rule(), universe.rule());
+    assert_eq!(live_coordinates(&round_trip), live_coordinates(&universe));
+}
+
+#[wasm_bindgen_test]
+pub fn test_glider_to_rle() {
+    let glider = "x = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n";
+    assert_eq!(Universe::from_rle(glider).unwrap().to_rle(), glider);
+    assert_rle_round_trip(glider);
+}
+
+#[wasm_bindgen_test]
+pub fn test_pulsar_to_rle() {
+    assert_rle_round_trip(
+        "x = 13, y = 13, rule = B3/S23
+2b3o3b3o2b2$o4bobo4bo$o4bobo4bo$o4bobo4bo$2b3o3b3o2b2$2b3o3b3o2b$o4bobo4bo$
+o4bobo4bo$o4bobo4bo2$2b3o3b3o!",
+    );
+}
+
+#[wasm_bindgen_test]
+pub fn test_empty_rle_round_trip() {
+    let rle = empty_universe(10, 10).to_rle();
+    assert_eq!(rle, "x = 0, y = 0, rule = B3/S23\n!\n");
+    let universe = Universe::from_rle(&rle).unwrap();
+    assert_eq!(universe, empty_universe(1, 1));
+    assert_eq!(universe.to_rle(), rle);
+}
+
+#[wasm_bindgen_test]
+pub fn test_to_rle_crops_to_live_cells() {
+    let mut universe = empty_universe(10, 10);
+    universe.set_cells(&[(4, 5), (5, 6), (6, 4), (6, 5), (6, 6)]);
+    assert_eq!(
+        universe.to_rle(),
+        "x = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n"
+    );
+}
+
+#[wasm_bindgen_test]
+pub fn test_glider_from_plaintext() {
+    let universe = Universe::from_plaintext(
+        "!Name: Glider
+!
+.O
+..O
+OOO
+",
+    )
+    .unwrap();
+    assert_eq!(universe.width(), 3);
+    assert_eq!(universe.height(), 3);
+    assert_eq!(
+        live_coordinates(&universe),
+        vec![(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]
+    );
+}
+
+#[wasm_bindgen_test]
+pub fn test_malformed_plaintext() {
+    assert!(Universe::from_plaintext("!Only a comment\n").is_err());
+    assert!(Universe::from_plaintext(".O\nO*\n").is_err());
+}
+
+#[wasm_bindgen_test]
+pub fn test_from_grid_str() {
+    let universe = Universe::from_grid_str(" #\n  #\n###", '#');
+    assert_eq!(universe.width(), 3);
+    assert_eq!(universe.height(), 3);
+    assert_eq!(live_coordinates(&universe), GLIDER.to_vec());
+
+    // Anything but the live character is dead, including `O`
+    let universe = Universe::from_grid_str("xO\nx", 'x');
+    assert_eq!((universe.width(), universe.height()), (2, 2));
+    assert_eq!(live_coordinates(&universe), vec![(0, 0), (1, 0)]);
+
+    assert_eq!(Universe::from_grid_str("", '#'), empty_universe(1, 1));
+}
+
+#[wasm_bindgen_test]
+pub fn test_random_is_deterministic() {
+    let universe = Universe::random(32, 16, 42);
+    assert_eq!(universe.width(), 32);
+    assert_eq!(universe.height(), 16);
+    assert_eq!(
+        universe.get_cells(),
+        Universe::random(32, 16, 42).get_cells()
+    );
+    assert_ne!(
+        universe.get_cells(),
+        Universe::random(32, 16, 43).get_cells()
+    );
+}
+
+#[wasm_bindgen_test]
+pub fn test_random_zero_size() {
+    let universe = Universe::random(0, 4, 1);
+    assert_eq!((universe.width(), universe.height()), (1, 4));
+    // Wrapping around a zero-wide universe would divide by zero
+    universe.get_cell(2, 3);
+}
+
+#[wasm_bindgen_test]
+pub fn test_generation() {
+    let mut universe = Universe::random(16, 16, 7);
+    assert_eq!(universe.generation(), 0);
+    for _ in 0..5 {
+        universe.tick();
+    }
+    assert_eq!(universe.generation(), 5);
+
+    universe.set_width(8);
+    assert_eq!(universe.generation(), 0);
+}
+
+#[wasm_bindgen_test]
+pub fn test_population() {
+    let mut universe = empty_universe(8, 4);
+    assert_eq!(universe.population(), 0);
+
+    universe.toggle_cell(0, 0);
+    universe.toggle_cell(3, 7);
+    universe.toggle_cell(2, 5);
+    universe.toggle_cell(0, 0);
+    assert_eq!(universe.population(), 2);
+
+    let all: Vec<(u32, u32)> = (0..4)
+        .flat_map(|row| (0..8).map(move |col| (row, col)))
+        .collect();
+    universe.set_cells(&all);
+    assert_eq!(universe.population(), 32);
+}
+
+#[wasm_bindgen_test]
+pub fn test_clear() {
+    let mut universe = Universe::random(12, 10, 3);
+    universe.tick();
+    universe.set_cells(&[(1, 1), (2, 2), (3, 3)]);
+    universe.clear();
+    assert_eq!(universe.population(), 0);
+    assert_eq!(universe.width(), 12);
+    assert_eq!(universe.height(), 10);
+    assert_eq!(universe.generation(), 1);
+}
+
+#[wasm_bindgen_test]
+pub fn test_insert_glider() {
+    let mut universe = empty_universe(8, 8);
+    // This cell is inside the glider's box and gets overwritten
+    universe.set_cells(&[(3, 4)]);
+    universe.insert_glider(3, 4);
+    assert_eq!(
+        live_coordinates(&universe),
+        vec![(3, 5), (4, 6), (5, 4), (5, 5), (5, 6)]
+    );
+}
+
+#[wasm_bindgen_test]
+pub fn test_insert_glider_wraps() {
+    let mut universe = empty_universe(8, 8);
+    universe.insert_glider(7, 7);
+    assert_eq!(
+        live_coordinates(&universe),
+        vec![(0, 1), (1, 0), (1, 1), (1, 7), (7, 0)]
+    );
+}
+
+#[wasm_bindgen_test]
+pub fn test_insert_mode() {
+    let mut universe = empty_universe(8, 8);
+    assert_eq!(universe.insert_mode(), InsertMode::Wrap);
+    universe.insert_glider(6, 6);
+    assert_eq!(
+        live_coordinates(&universe),
+        vec![(0, 0), (0, 6), (0, 7), (6, 7), (7, 0)]
+    );
+
+    universe.clear();
+    universe.set_insert_mode(InsertMode::Clip);
+    // Inside the glider's box, so still overwritten when clipping
+    universe.set_cells(&[(7, 7)]);
+    universe.insert_glider(6, 6);
+    assert_eq!(live_coordinates(&universe), vec![(6, 7)]);
+
+    universe.clear();
+    universe.insert_pattern(7, 6, &GLIDER);
+    assert_eq!(live_coordinates(&universe), vec![(7, 7)]);
+}
+
+#[wasm_bindgen_test]
+pub fn test_insert_pattern() {
+    let blinker = [(0, 0), (0, 1), (0, 2)];
+    let mut universe = empty_universe(6, 6);
+    universe.insert_pattern(1, 1, &blinker);
+    assert_eq!(live_coordinates(&universe), vec![(1, 1), (1, 2), (1, 3)]);
+
+    universe.clear();
+    universe.insert_pattern(5, 4, &blinker);
+    assert_eq!(live_coordinates(&universe), vec![(5, 0), (5, 4), (5, 5)]);
+}
+
+#[cfg(test)]
+pub const GLIDER: [(u32, u32); 5] = [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)];
+
+#[wasm_bindgen_test]
+pub fn test_rotate_pattern() {
+    // A quarter turn clockwise makes the glider head south-west
+    let mut rotated = rotate_pattern(&GLIDER, 1);
+    rotated.sort_unstable();
+    assert_eq!(rotated, vec![(0, 0), (1, 0), (1, 2), (2, 0), (2, 1)]);
+
+    let mut shape = GLIDER.to_vec();
+    for _ in 0..4 {
+        shape = rotate_pattern(&shape, 1);
+    }
+    shape.sort_unstable();
+    assert_eq!(shape, GLIDER.to_vec());
+    assert_eq!(rotate_pattern(&GLIDER, 4), GLIDER.to_vec());
+}
+
+#[wasm_bindgen_test]
+pub fn test_insert_rotated_pattern() {
+    let mut universe = empty_universe(8, 8);
+    universe.insert_pattern(2, 3, &rotate_pattern(&GLIDER, 2));
+    // Half a turn makes it head north-west
+    assert_eq!(
+        live_coordinates(&universe),
+        vec![(2, 3), (2, 4), (2, 5), (3, 3), (4, 4)]
+    );
+}
+
+#[wasm_bindgen_test]
+pub fn test_tick_many() {
+    let mut stepped = Universe::random(24, 24, 11);
+    for _ in 0..10 {
+        stepped.tick();
+    }
+    let mut fast_forwarded = Universe::random(24, 24, 11);
+    fast_forwarded.tick_many(10);
+    assert_eq!(fast_forwarded.get_cells(), stepped.get_cells());
+    assert_eq!(fast_forwarded.generation(), 10);
+}
+
+#[wasm_bindgen_test]
+pub fn test_cells_changed() {
+    let mut block = empty_universe(6, 6);
+    block.set_cells(&[(2, 2), (2, 3), (3, 2), (3, 3)]);
+    block.tick();
+    assert_eq!(block.cells_changed(), 0);
+
+    // A blinker flips two cells off and two on every generation
+    let mut blinker = empty_universe(6, 6);
+    blinker.set_cells(&[(2, 1), (2, 2), (2, 3)]);
+    blinker.tick();
+    assert_eq!(blinker.cells_changed(), 4);
+}
+
+#[wasm_bindgen_test]
+pub fn test_is_stable() {
+    let mut block = empty_universe(6, 6);
+    block.set_cells(&[(2, 2), (2, 3), (3, 2), (3, 3)]);
+    assert!(!block.is_stable());
+    block.tick();
+    assert!(block.is_stable());
+
+    let mut blinker = empty_universe(6, 6);
+    blinker.set_cells(&[(2, 1), (2, 2), (2, 3)]);
+    for _ in 0..6 {
+        blinker.tick();
+        assert!(!blinker.is_stable());
+    }
+}
+
+#[wasm_bindgen_test]
+pub fn test_detect_blinker_period() {
+    let mut blinker = empty_universe(5, 5);
+    blinker.set_cells(&[(2, 1), (2, 2), (2, 3)]);
+    let hash = blinker.state_hash();
+    assert_eq!(blinker.detect_period(10), Some(2));
+
+    // Detection leaves the universe as it was
+    assert_eq!(blinker.state_hash(), hash);
+    assert_eq!(blinker.generation(), 0);
+}
+
+#[wasm_bindgen_test]
+pub fn test_detect_pulsar_period() {
+    let pulsar = Universe::from_rle(
+        "x = 13, y = 13
+2b3o3b3o2b2$o4bobo4bo$o4bobo4bo$o4bobo4bo$2b3o3b3o2b2$2b3o3b3o2b$o4bobo4bo$
+o4bobo4bo$o4bobo4bo2$2b3o3b3o!",
+    )
+    .unwrap();
+    let mut universe = empty_universe(17, 17);
+    universe.insert_pattern(2, 2, &live_coordinates(&pulsar));
+    assert_eq!(universe.detect_period(10), Some(3));
+    assert_eq!(universe.detect_period(2), None);
+}
+
+#[wasm_bindgen_test]
+pub fn test_undo() {
+    let mut universe = Universe::random(16, 16, 5);
+    let mut states = vec![universe.get_cells().clone()];
+    for _ in 0..3 {
+        universe.tick();
+        states.push(universe.get_cells().clone());
+    }
+
+    assert!(universe.undo());
+    assert_eq!(universe.get_cells(), &states[2]);
+    assert!(universe.undo());
+    assert_eq!(universe.get_cells(), &states[1]);
+    assert_eq!(universe.generation(), 1);
+    // The counts of the undone tick are gone
+    assert_eq!(universe.cells_changed(), 0);
+    assert_eq!(universe.last_births(), 0);
+    assert!(universe.changed_cells().is_empty());
+}
+
+#[wasm_bindgen_test]
+pub fn test_history_limit() {
+    let mut universe = Universe::random(16, 16, 5);
+    universe.set_history_limit(2);
+    universe.tick_many(5);
+    assert!(universe.undo());
+    assert!(universe.undo());
+    assert!(!universe.undo());
+    assert_eq!(universe.generation(), 3);
+
+    universe.set_history_limit(0);
+    universe.tick();
+    assert!(!universe.undo());
+}
+
+#[wasm_bindgen_test]
+pub fn test_bytes_round_trip() {
+    let mut universe = Universe::random(50, 30, 19);
+    universe.tick_many(3);
+    let decoded = Universe::from_bytes(&universe.to_bytes()).unwrap();
+    assert_eq!(decoded.width(), 50);
+    assert_eq!(decoded.height(), 30);
+    assert_eq!(decoded.generation(), 3);
+    assert_eq!(decoded.get_cells(), universe.get_cells());
+}
+
+#[wasm_bindgen_test]
+pub fn test_malformed_bytes() {
+    let bytes = Universe::random(50, 30, 19).to_bytes();
+    assert!(Universe::from_bytes(&bytes[..10]).is_err());
+    assert!(Universe::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+    let mut wrong_version = bytes.clone();
+    wrong_version[0] = 0;
+    assert!(Universe::from_bytes(&wrong_version).is_err());
+}
+
+#[wasm_bindgen_test]
+pub fn test_get_cell() {
+    let mut universe = empty_universe(5, 4);
+    universe.set_cells(&[(1, 2), (3, 4)]);
+    assert_eq!(universe.get_cell(1, 2), Cell::Alive);
+    assert_eq!(universe.get_cell(3, 4), Cell::Alive);
+    assert_eq!(universe.get_cell(0, 0), Cell::Dead);
+    assert_eq!(universe.get_cell(2, 4), Cell::Dead);
+
+    // Out of range coordinates wrap around
+    assert_eq!(universe.get_cell(5, 7), Cell::Alive);
+    assert_eq!(universe.get_cell(7, 9), Cell::Alive);
+    assert_eq!(universe.get_cell(4, 0), Cell::Dead);
+}
+
+#[wasm_bindgen_test]
+pub fn test_live_bounds() {
+    let mut universe = empty_universe(10, 8);
+    assert_eq!(universe.live_bounds(), None);
+
+    universe.set_cells(&[(1, 7), (4, 2), (6, 5)]);
+    assert_eq!(universe.live_bounds(), Some((1, 2, 6, 7)));
+}
+
+#[wasm_bindgen_test]
+pub fn test_live_cells() {
+    let mut universe = empty_universe(7, 9);
+    universe.set_cells(&[(8, 6), (0, 3), (4, 4), (4, 0), (2, 6)]);
+    assert_eq!(
+        universe.live_cells(),
+        vec![(0, 3), (2, 6), (4, 0), (4, 4), (8, 6)]
+    );
+}
+
+#[wasm_bindgen_test]
+pub fn test_resize() {
+    let mut universe = empty_universe(6, 6);
+    universe.set_cells(&[(0, 0), (1, 4), (3, 2), (5, 5)]);
+
+    universe.resize(4, 5);
+    assert_eq!(universe.width(), 4);
+    assert_eq!(universe.height(), 5);
+    assert_eq!(universe.live_cells(), vec![(0, 0), (3, 2)]);
+    assert_eq!(universe.get_cells().ones().collect::<Vec<_>>(), vec![0, 14]);
+
+    universe.resize(10, 7);
+    assert_eq!(universe.live_cells(), vec![(0, 0), (3, 2)]);
+    assert_eq!(universe.get_cells().len(), 70);
+    assert_eq!(universe.get_cells().ones().collect::<Vec<_>>(), vec![0, 32]);
+}
+
+#[wasm_bindgen_test]
+pub fn test_set_width_and_height_reallocate() {
+    let mut universe = Universe::new();
+    universe.set_width(200);
+    universe.set_height(150);
+    assert_eq!(universe.get_cells().len(), 200 * 150);
+    assert_eq!(universe.get_cell(149, 199), Cell::Dead);
+    universe.toggle_cell(149, 199);
+    assert_eq!(universe.get_cell(149, 199), Cell::Alive);
+
+    // Shrinking must not leave stale cells behind
+    universe.set_width(3);
+    assert_eq!(universe.get_cells().len(), 3 * 150);
+    assert_eq!(universe.population(), 0);
+}
+
+#[wasm_bindgen_test]
+pub fn test_new_with_size() {
+    let universe = Universe::new_with_size(10, 20);
+    assert_eq!(universe.width(), 10);
+    assert_eq!(universe.height(), 20);
+    assert_eq!(universe.population(), 0);
+
+    let universe = Universe::new_with_size(0, 5);
+    assert_eq!(universe.width(), 1);
+    assert_eq!(universe.height(), 5);
+}
+
+#[wasm_bindgen_test]
+pub fn test_single_row_neighbors() {
+    let mut universe = empty_universe(5, 1);
+    universe.set_cells(&[(0, 0), (0, 1), (0, 2), (0, 3), (0, 4)]);
+    for col in 0..5 {
+        assert_eq!(universe.live_neighbor_count(0, col), 2);
+    }
+
+    let mut universe = empty_universe(2, 1);
+    universe.set_cells(&[(0, 0), (0, 1)]);
+    assert_eq!(universe.live_neighbor_count(0, 0), 1);
+}
+
+#[wasm_bindgen_test]
+pub fn test_single_column_neighbors() {
+    let mut universe = empty_universe(1, 4);
+    universe.set_cells(&[(0, 0), (1, 0), (2, 0), (3, 0)]);
+    for row in 0..4 {
+        assert_eq!(universe.live_neighbor_count(row, 0), 2);
+    }
+
+    let mut universe = empty_universe(1, 1);
+    universe.set_cells(&[(0, 0)]);
+    assert_eq!(universe.live_neighbor_count(0, 0), 0);
+}
+
+#[wasm_bindgen_test]
+pub fn test_two_by_two_neighbors() {
+    let mut universe = empty_universe(2, 2);
+    universe.set_cells(&[(0, 0), (0, 1), (1, 0), (1, 1)]);
+    assert_eq!(universe.live_neighbor_count(0, 0), 3);
+}
+
+#[wasm_bindgen_test]
+pub fn test_ages() {
+    let mut blinker = empty_universe(5, 5);
+    blinker.set_cells(&[(2, 1), (2, 2), (2, 3)]);
+    let center = 2 * 5 + 2;
+    let west = 2 * 5 + 1;
+    let north = 5 + 2;
+
+    blinker.tick();
+    assert_eq!(blinker.get_ages()[center], 1);
+    assert_eq!(blinker.get_ages()[west], 0);
+    assert_eq!(blinker.get_ages()[north], 1);
+
+    blinker.tick();
+    assert_eq!(blinker.get_ages()[center], 2);
+    assert_eq!(blinker.get_ages()[west], 1);
+    assert_eq!(blinker.get_ages()[north], 0);
+
+    blinker.tick();
+    assert_eq!(blinker.get_ages()[center], 3);
+    assert_eq!(blinker.get_ages()[west], 0);
+    assert_eq!(blinker.get_ages()[north], 1);
+}
+
+/// Straightforward next generation to compare `tick` against
+#[cfg(test)]
+pub fn reference_tick(universe: &Universe) -> Vec<(u32, u32)> {
+    let rule = universe.rule();
+    let mut live = Vec::new();
+    for row in 0..universe.height() {
+        for col in 0..universe.width() {
+            let alive = universe.get_cell(row, col) == Cell::Alive;
+            let neighbors = universe.live_neighbor_count(row, col);
+            if rule.next_state(alive, neighbors) {
+                live.push((row, col));
+            }
+        }
+    }
+    live
+}
+
+#[wasm_bindgen_test]
+pub fn test_tick_matches_reference() {
+    for &(width, height, seed) in &[(16, 16, 1), (33, 17, 2), (64, 40, 3)] {
+        let mut universe = Universe::random(width, height, seed);
+        for limit in &[0, 1, 64] {
+            universe.set_history_limit(*limit);
+            for _ in 0..5 {
+                let expected = reference_tick(&universe);
+                universe.tick();
+                assert_eq!(universe.live_cells(), expected);
+            }
+        }
+        universe.resize(width + 5, height + 3);
+        let expected = reference_tick(&universe);
+        universe.tick();
+        assert_eq!(universe.live_cells(), expected);
+    }
+}
+
+#[wasm_bindgen_test]
+pub fn test_diff() {
+    let mut a = empty_universe(6, 5);
+    let mut b = empty_universe(6, 5);
+    a.set_cells(&[(0, 0), (1, 1), (2, 2), (4, 5)]);
+    b.set_cells(&[(0, 0), (2, 2), (3, 0), (4, 4), (4, 5)]);
+    assert_eq!(a.diff(&b), vec![(1, 1), (3, 0), (4, 4)]);
+    assert_eq!(a.diff(&a), vec![]);
+}
+
+#[wasm_bindgen_test]
+#[should_panic(expected = "cannot diff a 6x5 universe against a 5x6 one")]
+pub fn test_diff_mismatched_dimensions() {
+    empty_universe(6, 5).diff(&empty_universe(5, 6));
+}
+
+#[wasm_bindgen_test]
+pub fn test_clone_and_eq() {
+    let universe = Universe::random(20, 20, 31);
+    let mut ticked = universe.clone();
+    assert_eq!(ticked, universe);
+    ticked.tick();
+    assert_ne!(ticked, universe);
+
+    // The same cells at a different generation are not equal either
+    let mut still_life = empty_universe(6, 6);
+    still_life.set_cells(&[(2, 2), (2, 3), (3, 2), (3, 3)]);
+    let mut ticked = still_life.clone();
+    ticked.tick();
+    assert_eq!(ticked.get_cells(), still_life.get_cells());
+    assert_ne!(ticked, still_life);
+}
+
+#[wasm_bindgen_test]
+pub fn test_to_grayscale() {
+    let mut universe = empty_universe(7, 3);
+    universe.set_cells(&[(0, 1), (2, 6)]);
+    let pixels = universe.to_grayscale();
+    assert_eq!(pixels.len(), 21);
+    assert_eq!(pixels[0], 0);
+    assert_eq!(pixels[1], 255);
+    assert_eq!(pixels[20], 255);
+    assert_eq!(pixels.iter().filter(|&&pixel| pixel == 255).count(), 2);
+}
+
+#[wasm_bindgen_test]
+pub fn test_neighborhoods() {
+    let mut universe = empty_universe(5, 5);
+    // Every cell around (2, 2), two of them orthogonal
+    universe.set_cells(&[(1, 1), (1, 2), (1, 3), (2, 3), (3, 1), (3, 3)]);
+    assert_eq!(universe.neighborhood(), Neighborhood::Moore);
+    assert_eq!(universe.live_neighbor_count(2, 2), 6);
+
+    universe.set_neighborhood(Neighborhood::VonNeumann);
+    assert_eq!(universe.live_neighbor_count(2, 2), 2);
+}
+
+#[wasm_bindgen_test]
+pub fn test_brians_brain_generations() {
+    let mut universe = empty_universe(8, 8);
+    universe.set_rule(Rule::parse("B2/S").unwrap());
+    universe.set_rule_family(RuleFamily::Generations { states: 3 });
+    universe.set_cells(&[(3, 3), (3, 4)]);
+    assert_eq!(universe.cell_state(3, 3), 1);
+
+    // Live cells never survive, but fade out through the dying state
+    universe.tick();
+    assert_eq!(universe.cell_state(3, 3), 2);
+    assert_eq!(universe.cell_state(3, 4), 2);
+    // Cells next to exactly two live cells are born
+    assert_eq!(universe.cell_state(2, 3), 1);
+    assert_eq!(universe.cell_state(4, 4), 1);
+    assert_eq!(universe.population(), 4);
+
+    universe.tick();
+    assert_eq!(universe.cell_state(3, 3), 0);
+    assert_eq!(universe.cell_state(2, 3), 2);
+
+    universe.tick();
+    assert_eq!(universe.cell_state(2, 3), 0);
+}
+
+#[wasm_bindgen_test]
+pub fn test_brians_brain() {
+    let mut universe = empty_universe(8, 8);
+    universe.set_rule_family(RuleFamily::BriansBrain);
+    universe.set_cell_state(3, 3, 1);
+    universe.set_cell_state(3, 4, 1);
+    // A dying cell is not a live neighbor, so it does not add births
+    universe.set_cell_state(5, 5, 2);
+    assert_eq!(universe.cell_state(5, 5), 2);
+    assert_eq!(universe.population(), 2);
+
+    universe.tick();
+    assert_eq!(universe.cell_state(3, 3), 2);
+    assert_eq!(universe.cell_state(5, 5), 0);
+    assert_eq!(universe.cell_state(2, 3), 1);
+    assert_eq!(universe.cell_state(4, 4), 1);
+    assert_eq!(universe.population(), 4);
+
+    universe.tick();
+    assert_eq!(universe.cell_state(3, 3), 0);
+    assert_eq!(universe.cell_state(2, 3), 2);
+
+    universe.tick();
+    assert_eq!(universe.cell_state(2, 3), 0);
+
+    // The same game as the equivalent Generations rule
+    let mut brain = Universe::random(16, 16, 3);
+    brain.set_rule_family(RuleFamily::BriansBrain);
+    let mut generations = Universe::random(16, 16, 3);
+    generations.set_rule(Rule::parse("B2/S").unwrap());
+    generations.set_rule_family(RuleFamily::Generations { states: 3 });
+    for _ in 0..6 {
+        brain.tick();
+        generations.tick();
+        for row in 0..16 {
+            for col in 0..16 {
+                assert_eq!(brain.cell_state(row, col), generations.cell_state(row, col));
+            }
+        }
+    }
+}
+
+#[wasm_bindgen_test]
+pub fn test_set_cell_state() {
+    let mut universe = empty_universe(4, 4);
+    universe.set_cell_state(1, 1, 1);
+    assert_eq!(universe.get_cell(1, 1), Cell::Alive);
+    // Life-like rules have no dying states
+    universe.set_cell_state(1, 1, 2);
+    assert_eq!(universe.cell_state(1, 1), 0);
+
+    universe.set_rule_family(RuleFamily::Generations { states: 4 });
+    universe.set_cell_state(1, 1, 3);
+    assert_eq!(universe.cell_state(1, 1), 3);
+    assert_eq!(universe.get_cell(1, 1), Cell::Dead);
+    universe.set_cell_state(1, 1, 4);
+    assert_eq!(universe.cell_state(1, 1), 0);
+}
+
+#[wasm_bindgen_test]
+pub fn test_edits_forget_dying_states() {
+    let mut universe = empty_universe(6, 6);
+    universe.set_rule_family(RuleFamily::BriansBrain);
+    universe.set_cell_state(1, 1, 2);
+    universe.clear();
+    assert_eq!(universe.cell_state(1, 1), 0);
+
+    // Toggling a dying cell twice leaves it dead
+    universe.set_rule(Rule::parse("B2/S").unwrap());
+    universe.set_rule_family(RuleFamily::Generations { states: 4 });
+    universe.set_cell_state(1, 1, 3);
+    universe.toggle_cell(1, 1);
+    assert_eq!(universe.cell_state(1, 1), 1);
+    universe.toggle_cell(1, 1);
+    assert_eq!(universe.cell_state(1, 1), 0);
+
+    // Moving cells takes their dying states along
+    universe.set_cell_state(1, 1, 2);
+    universe.translate(2, 1);
+    assert_eq!(universe.cell_state(1, 1), 0);
+    assert_eq!(universe.cell_state(3, 2), 2);
+    universe.flip_horizontal();
+    assert_eq!(universe.cell_state(3, 3), 2);
+    universe.flip_vertical();
+    assert_eq!(universe.cell_state(2, 3), 2);
+}
+
+#[wasm_bindgen_test]
+pub fn test_generations_with_two_states_is_life_like() {
+    let mut life = Universe::random(20, 20, 8);
+    let mut generations = life.clone();
+    generations.set_rule_family(RuleFamily::Generations { states: 2 });
+    life.tick_many(5);
+    generations.tick_many(5);
+    assert_eq!(generations.get_cells(), life.get_cells());
+}
+
+#[wasm_bindgen_test]
+pub fn test_set_all_cells() {
+    let universe = Universe::random(13, 9, 35);
+    let mut copy = empty_universe(13, 9);
+    copy.set_all_cells(&universe.to_grayscale());
+    assert_eq!(copy.get_cells(), universe.get_cells());
+
+    copy.set_all_cells(&[1; 13 * 9]);
+    assert_eq!(copy.population(), 13 * 9);
+}
+
+#[wasm_bindgen_test]
+#[should_panic(expected = "expected one byte for each cell of the 13x9 universe")]
+pub fn test_set_all_cells_length_mismatch() {
+    empty_universe(13, 9).set_all_cells(&[0; 13 * 8]);
+}
+
+#[wasm_bindgen_test]
+pub fn test_toggle_cells() {
+    let mut universe = empty_universe(6, 6);
+    universe.set_cells(&[(1, 1), (4, 4)]);
+    let before = universe.clone();
+
+    // (1, 1) is listed twice, so only (1, 2) and (4, 4) flip
+    let brush = [(1, 1), (1, 2), (4, 4), (1, 1)];
+    universe.toggle_cells(&brush);
+    assert_eq!(live_coordinates(&universe), vec![(1, 1), (1, 2)]);
+
+    universe.toggle_cells(&brush);
+    assert_eq!(universe, before);
+}
+
+#[wasm_bindgen_test]
+pub fn test_set_cell() {
+    let mut universe = empty_universe(4, 4);
+    // Painting over an already alive cell keeps it alive
+    universe.set_cell(1, 2, true);
+    universe.set_cell(1, 2, true);
+    assert_eq!(universe.get_cell(1, 2), Cell::Alive);
+
+    universe.set_cell(1, 2, false);
+    universe.set_cell(1, 2, false);
+    assert_eq!(universe.get_cell(1, 2), Cell::Dead);
+}
+
+#[wasm_bindgen_test]
+pub fn test_invert() {
+    // 35 cells leave a partly used last word, which must stay clean
+    let mut universe = empty_universe(7, 5);
+    universe.invert();
+    assert_eq!(universe.population(), 35);
+    universe.invert();
+    assert_eq!(universe.population(), 0);
+
+    universe.set_cells(&[(0, 0), (4, 6)]);
+    universe.invert();
+    assert_eq!(universe.population(), 33);
+    assert_eq!(universe.get_cell(0, 0), Cell::Dead);
+    assert_eq!(universe.get_cell(2, 3), Cell::Alive);
+}
+
+#[wasm_bindgen_test]
+pub fn test_translate() {
+    let mut universe = empty_universe(8, 8);
+    universe.insert_pattern(2, 2, &GLIDER);
+    universe.translate(1, 1);
+    assert_eq!(
+        universe.live_cells(),
+        vec![(3, 4), (4, 5), (5, 3), (5, 4), (5, 5)]
+    );
+
+    // Moving off the top-left edge wraps around to the bottom-right
+    universe.translate(-4, -5);
+    assert_eq!(
+        universe.live_cells(),
+        vec![(0, 0), (1, 0), (1, 6), (1, 7), (7, 7)]
+    );
+}
+
+#[cfg(test)]
+pub const L_SHAPE: [(u32, u32); 4] = [(0, 0), (1, 0), (2, 0), (2, 1)];
+
+#[wasm_bindgen_test]
+pub fn test_flip_horizontal() {
+    for &width in &[4, 5] {
+        let mut universe = empty_universe(width, 3);
+        universe.set_cells(&L_SHAPE);
+        universe.flip_horizontal();
+        assert_eq!(
+            universe.live_cells(),
+            vec![
+                (0, width - 1),
+                (1, width - 1),
+                (2, width - 2),
+                (2, width - 1)
+            ]
+        );
+        universe.flip_horizontal();
+        assert_eq!(universe.live_cells(), L_SHAPE.to_vec());
+    }
+}
+
+#[wasm_bindgen_test]
+pub fn test_flip_vertical() {
+    for &height in &[4, 5] {
+        let mut universe = empty_universe(3, height);
+        universe.set_cells(&L_SHAPE);
+        universe.flip_vertical();
+        assert_eq!(
+            universe.live_cells(),
+            vec![
+                (height - 3, 0),
+                (height - 3, 1),
+                (height - 2, 0),
+                (height - 1, 0)
+            ]
+        );
+    }
+}
+
+#[wasm_bindgen_test]
+pub fn test_transpose() {
+    let mut universe = empty_universe(5, 3);
+    universe.set_cells(&L_SHAPE);
+    universe.set_cell(0, 4, true);
+    universe.transpose();
+    assert_eq!((universe.width(), universe.height()), (3, 5));
+    assert_eq!(
+        universe.live_cells(),
+        vec![(0, 0), (0, 1), (0, 2), (1, 2), (4, 0)]
+    );
+
+    universe.transpose();
+    assert_eq!((universe.width(), universe.height()), (5, 3));
+    let mut expected = L_SHAPE.to_vec();
+    expected.push((0, 4));
+    expected.sort();
+    assert_eq!(universe.live_cells(), expected);
+}
+
+#[wasm_bindgen_test]
+pub fn test_rotate90() {
+    let mut universe = empty_universe(3, 3);
+    universe.set_cells(&GLIDER);
+    universe.rotate90(true);
+    let mut expected = rotate_pattern(&GLIDER, 1);
+    expected.sort_unstable();
+    assert_eq!(universe.live_cells(), expected);
+    universe.rotate90(false);
+    assert_eq!(universe.live_cells(), GLIDER.to_vec());
+
+    let mut universe = empty_universe(6, 4);
+    universe.set_cells(&GLIDER);
+    let before = universe.clone();
+    universe.rotate90(true);
+    assert_eq!((universe.width(), universe.height()), (4, 6));
+    // The glider heading south-west, one column in since the dead row
+    // below it turns into a column on its left
+    assert_eq!(
+        universe.live_cells(),
+        vec![(0, 1), (1, 1), (1, 3), (2, 1), (2, 2)]
+    );
+    for _ in 0..3 {
+        universe.rotate90(true);
+    }
+    assert_eq!(universe, before);
+}
+
+#[wasm_bindgen_test]
+pub fn test_subgrid() {
+    let mut universe = empty_universe(10, 10);
+    universe.set_rule(Rule::parse("B36/S23").unwrap());
+    universe.set_cells(&[(0, 0), (5, 4), (5, 5), (5, 6), (9, 9)]);
+
+    let mut blinker = universe.subgrid(4, 4, 3, 3);
+    assert_eq!(blinker.width(), 3);
+    assert_eq!(blinker.height(), 3);
+    assert_eq!(blinker.rule(), universe.rule());
+    assert_eq!(blinker.live_cells(), vec![(1, 0), (1, 1), (1, 2)]);
+    blinker.set_boundary_mode(BoundaryMode::Dead);
+    blinker.tick();
+    assert_eq!(blinker.live_cells(), vec![(0, 1), (1, 1), (2, 1)]);
+
+    // Regions beyond the edges are clamped
+    let corner = universe.subgrid(8, 8, 5, 5);
+    assert_eq!(corner.width(), 2);
+    assert_eq!(corner.height(), 2);
+    assert_eq!(corner.live_cells(), vec![(1, 1)]);
+}
+
+#[wasm_bindgen_test]
+pub fn test_crop_to_live() {
+    let mut universe = empty_universe(50, 50);
+    universe.crop_to_live();
+    assert_eq!((universe.width(), universe.height()), (50, 50));
+
+    universe.insert_pattern(24, 23, &GLIDER);
+    universe.crop_to_live();
+    assert_eq!((universe.width(), universe.height()), (3, 3));
+    assert_eq!(universe.live_cells(), GLIDER.to_vec());
+}
+
+#[wasm_bindgen_test]
+pub fn test_combine() {
+    let mut gliders = empty_universe(12, 12);
+    gliders.insert_pattern(1, 1, &GLIDER);
+    let mut other = empty_universe(12, 12);
+    other.insert_pattern(6, 6, &GLIDER);
+
+    gliders.combine(&other, BitOp::Or);
+    assert_eq!(gliders.population(), 10);
+    gliders.combine(&other, BitOp::And);
+    assert_eq!(gliders.live_cells(), other.live_cells());
+
+    let copy = gliders.clone();
+    gliders.combine(&copy, BitOp::Xor);
+    assert_eq!(gliders.population(), 0);
+}
+
+#[wasm_bindgen_test]
+#[should_panic(expected = "cannot combine a 12x12 universe with a 12x11 one")]
+pub fn test_combine_mismatched_dimensions() {
+    empty_universe(12, 12).combine(&empty_universe(12, 11), BitOp::Or);
+}
+
+#[wasm_bindgen_test]
+pub fn test_escaped_population() {
+    let mut universe = empty_universe(8, 8);
+    universe.set_boundary_mode(BoundaryMode::Dead);
+    universe.insert_glider(0, 0);
+    let mut untracked = universe.clone();
+    universe.set_track_escapes(true);
+    assert!(universe.track_escapes());
+    let escaped: Vec<u32> = (0..24)
+        .map(|_| {
+            universe.tick();
+            universe.escaped_population()
+        })
+        .collect();
+    // The glider reaches the bottom-right corner on the 21st tick, where one
+    // cell it would give birth to falls off, and it leaves a block behind
+    assert!(escaped[..20].iter().all(|&count| count == 0));
+    assert_eq!(escaped[20], 1);
+    assert!(escaped[21..].iter().all(|&count| count == 0));
+    assert_eq!(universe.population(), 4);
+
+    // Nothing is counted unless asked for
+    untracked.tick_many(21);
+    assert_eq!(untracked.escaped_population(), 0);
+
+    // Wrapping around, nothing is ever lost
+    let mut universe = empty_universe(8, 8);
+    universe.set_track_escapes(true);
+    universe.insert_glider(0, 0);
+    for _ in 0..24 {
+        universe.tick();
+        assert_eq!(universe.escaped_population(), 0);
+    }
+}
+
+#[wasm_bindgen_test]
+pub fn test_births_and_deaths() {
+    let mut blinker = empty_universe(5, 5);
+    blinker.set_cells(&[(2, 1), (2, 2), (2, 3)]);
+    blinker.tick();
+    assert_eq!(blinker.last_births(), 2);
+    assert_eq!(blinker.last_deaths(), 2);
+
+    // Every cell of a lone pair dies without any births
+    let mut pair = empty_universe(5, 5);
+    pair.set_cells(&[(2, 2), (2, 3)]);
+    pair.tick();
+    assert_eq!(pair.last_births(), 0);
+    assert_eq!(pair.last_deaths(), 2);
+    assert_eq!(pair.cells_changed(), 2);
+}
+
+#[wasm_bindgen_test]
+pub fn test_stats() {
+    let mut universe = empty_universe(6, 6);
+    universe.set_cells(&[(2, 1), (2, 2), (2, 3), (5, 5)]);
+    universe.tick();
+    let stats = universe.stats();
+    // The blinker turns, and the lone cell dies
+    assert_eq!(
+        stats,
+        Stats {
+            generation: 1,
+            population: 3,
+            births: 2,
+            deaths: 3,
+            changed: 5,
+        }
+    );
+    assert_eq!(stats.generation, universe.generation());
+    assert_eq!(stats.population, universe.population());
+    assert_eq!(stats.births, universe.last_births());
+    assert_eq!(stats.deaths, universe.last_deaths());
+    assert_eq!(stats.changed, universe.cells_changed());
+}
+
+#[wasm_bindgen_test]
+pub fn test_changed_cells() {
+    let mut universe = empty_universe(8, 8);
+    universe.insert_pattern(1, 1, &GLIDER);
+    assert_eq!(universe.changed_cells(), vec![]);
+
+    universe.tick();
+    assert_eq!(
+        universe.changed_cells(),
+        vec![(1, 2), (2, 1), (3, 1), (4, 2)]
+    );
+    assert_eq!(universe.changed_indices(), vec![10, 17, 25, 34]);
+    assert_eq!(
+        universe.changed_cells().len() as u32,
+        universe.cells_changed()
+    );
+}
+
+#[cfg(test)]
+pub fn walled_universe() -> Universe {
+    let mut universe = empty_universe(12, 12);
+    universe.set_boundary_mode(BoundaryMode::Dead);
+    universe.insert_pattern(0, 0, &GLIDER);
+    for col in 0..12 {
+        universe.set_wall(7, col, true);
+    }
+    universe
+}
+
+#[wasm_bindgen_test]
+pub fn test_glider_hits_wall() {
+    let mut universe = walled_universe();
+    for _ in 0..40 {
+        universe.tick();
+        for col in 0..12 {
+            assert!(universe.is_wall(7, col));
+            assert_eq!(universe.get_cell(7, col), Cell::Dead);
+        }
+        // Nothing gets past the wall
+        for row in 8..12 {
+            for col in 0..12 {
+                assert_eq!(universe.get_cell(row, col), Cell::Dead);
+            }
+        }
+    }
+}
+
+#[wasm_bindgen_test]
+pub fn test_walls_alive() {
+    let mut universe = empty_universe(5, 5);
+    universe.set_cells(&[(2, 2)]);
+    universe.set_wall(1, 1, true);
+    universe.set_wall(1, 2, true);
+    assert_eq!(universe.live_neighbor_count(2, 1), 1);
+
+    universe.set_walls_alive(true);
+    assert_eq!(universe.live_neighbor_count(2, 1), 3);
+    // Walls never come alive, whatever their neighbors
+    universe.tick();
+    assert_eq!(universe.get_cell(1, 1), Cell::Dead);
+    assert_eq!(universe.get_cell(2, 1), Cell::Alive);
+}
+
+#[wasm_bindgen_test]
+pub fn test_edits_keep_walls_dead() {
+    let mut universe = empty_universe(4, 4);
+    universe.set_wall(1, 1, true);
+    universe.invert();
+    assert_eq!(universe.get_cell(1, 1), Cell::Dead);
+    assert_eq!(universe.population(), 15);
+
+    universe.clear();
+    universe.fill_rect(0, 0, 4, 4, true);
+    assert_eq!(universe.population(), 15);
+    universe.set_cell(1, 1, true);
+    universe.toggle_cell(1, 1);
+    universe.set_cells(&[(1, 1)]);
+    assert_eq!(universe.get_cell(1, 1), Cell::Dead);
+
+    // Moving a live cell onto a wall kills it
+    universe.clear();
+    universe.set_cell(1, 0, true);
+    universe.translate(0, 1);
+    assert_eq!(universe.population(), 0);
+}
+
+#[wasm_bindgen_test]
+pub fn test_to_svg() {
+    let mut universe = empty_universe(12, 8);
+    universe.insert_pattern(2, 3, &GLIDER);
+    let svg = universe.to_svg(10);
+    assert!(svg.starts_with("<svg "));
+    assert!(svg.contains("width=\"120\" height=\"80\" viewBox=\"0 0 120 80\""));
+    assert!(svg.contains("<rect x=\"40\" y=\"20\" width=\"10\" height=\"10\""));
+    // One rect per live cell on top of the background
+    let cells = svg.matches("<rect x=").count() as u32;
+    assert_eq!(cells, universe.population());
+    assert_eq!(svg.matches("<rect ").count() as u32, cells + 1);
+}
+
+#[wasm_bindgen_test]
+pub fn test_dying_states_count_as_changes() {
+    let mut universe = empty_universe(6, 6);
+    universe.set_rule(Rule::parse("B/S").unwrap());
+    universe.set_rule_family(RuleFamily::Generations { states: 5 });
+    universe.set_cell(2, 2, true);
+
+    universe.tick();
+    universe.tick();
+    // Still fading from state 2 to 3, without being or turning alive
+    assert_eq!(universe.cell_state(2, 2), 3);
+    assert_eq!(universe.cells_changed(), 1);
+    assert_eq!(universe.changed_cells(), vec![(2, 2)]);
+    assert!(!universe.is_stable());
+    assert!(universe.delta_rle().is_empty());
+
+    // Two more ticks to reach state 4 and then dead, and one unchanging tick
+    assert_eq!(universe.run_until_stable(10), 3);
+    assert_eq!(universe.cell_state(2, 2), 0);
+}
+
+#[wasm_bindgen_test]
+pub fn test_run_until_stable() {
+    let mut block = empty_universe(6, 6);
+    block.set_cells(&[(2, 2), (2, 3), (3, 2), (3, 3)]);
+    assert_eq!(block.run_until_stable(100), 1);
+    assert!(block.is_stable());
+
+    let mut glider = empty_universe(10, 10);
+    glider.insert_pattern(0, 0, &GLIDER);
+    assert_eq!(glider.run_until_stable(50), 50);
+    assert_eq!(glider.generation(), 50);
+    assert!(!glider.is_stable());
+}
+
+#[wasm_bindgen_test]
+pub fn test_fill_rect() {
+    let mut universe = empty_universe(10, 8);
+    universe.fill_rect(2, 3, 3, 4, true);
+    assert_eq!(universe.population(), 12);
+    assert_eq!(universe.live_bounds(), Some((2, 3, 5, 5)));
+
+    universe.fill_rect(3, 4, 1, 2, false);
+    assert_eq!(universe.population(), 10);
+    assert_eq!(universe.get_cell(3, 4), Cell::Dead);
+
+    // Clamped at the bottom-right edges
+    universe.clear();
+    universe.fill_rect(6, 8, 5, 5, true);
+    assert_eq!(universe.live_cells(), vec![(6, 8), (6, 9), (7, 8), (7, 9)]);
+}
+
+#[wasm_bindgen_test]
+pub fn test_draw_line() {
+    let mut universe = empty_universe(8, 8);
+    universe.draw_line(1, 1, 5, 5, true);
+    assert_eq!(
+        universe.live_cells(),
+        vec![(1, 1), (2, 2), (3, 3), (4, 4), (5, 5)]
+    );
+
+    // Shallow lines leave no gaps between columns
+    universe.clear();
+    universe.draw_line(4, 6, 2, 0, true);
+    assert_eq!(
+        universe.live_cells(),
+        vec![(2, 0), (2, 1), (3, 2), (3, 3), (3, 4), (4, 5), (4, 6)]
+    );
+
+    universe.draw_line(3, 2, 3, 4, false);
+    assert_eq!(universe.population(), 4);
+}
+
+#[wasm_bindgen_test]
+pub fn test_pattern_by_name() {
+    let expected = [
+        ("glider", 5, 3, 3),
+        ("blinker", 3, 1, 3),
+        ("toad", 6, 2, 4),
+        ("beacon", 8, 4, 4),
+        ("pulsar", 48, 13, 13),
+        ("lwss", 9, 4, 5),
+        ("gosper_glider_gun", 36, 9, 36),
+    ];
+    for &(name, population, height, width) in expected.iter() {
+        let cells = pattern_by_name(name).unwrap();
+        assert_eq!(cells.len(), population, "{}", name);
+        let rows = cells.iter().map(|&(row, _)| row).max().unwrap() + 1;
+        let cols = cells.iter().map(|&(_, col)| col).max().unwrap() + 1;
+        assert_eq!((rows, cols), (height, width), "{}", name);
+    }
+    assert_eq!(pattern_by_name("Glider"), Some(GLIDER.to_vec()));
+    assert_eq!(pattern_by_name("unknown"), None);
+
+    // The gun fires its first glider within 30 generations
+    let mut universe = empty_universe(60, 40);
+    universe.insert_pattern(1, 1, &pattern_by_name("gosper_glider_gun").unwrap());
+    universe.tick_many(30);
+    assert_eq!(universe.population(), 36 + 5);
+}
+
+#[wasm_bindgen_test]
+pub fn test_randomize() {
+    let mut universe = empty_universe(20, 10);
+    universe.randomize(0.0, 7);
+    assert_eq!(universe.population(), 0);
+    universe.randomize(1.0, 7);
+    assert_eq!(universe.population(), 200);
+    universe.randomize(-3.0, 7);
+    assert_eq!(universe.population(), 0);
+
+    universe.randomize(0.5, 42);
+    let mut other = empty_universe(20, 10);
+    other.randomize(0.5, 42);
+    assert_eq!(universe, other);
+    assert!(universe.population() > 50 && universe.population() < 150);
+
+    other.randomize(0.5, 43);
+    assert_ne!(universe, other);
+}
+
+#[wasm_bindgen_test]
+pub fn test_new_with_seed_pattern() {
+    let population = |pattern| Universe::new_with_seed_pattern(70, 30, pattern).population();
+    // 2100 cells, every seventh of them alive
+    assert_eq!(population(SeedPattern::Sparse), 300);
+    assert!((1150..=1250).contains(&population(SeedPattern::Dense)));
+    assert_eq!(population(SeedPattern::Checkerboard), 1050);
+    assert_eq!(population(SeedPattern::Empty), 0);
+
+    assert_eq!(
+        Universe::new_with_seed_pattern(128, 128, SeedPattern::Dense),
+        Universe::new()
+    );
+    let board = Universe::new_with_seed_pattern(3, 2, SeedPattern::Checkerboard);
+    assert_eq!(board.live_cells(), vec![(0, 0), (0, 2), (1, 1)]);
+    // Each pattern is what resetting goes back to
+    let mut universe = Universe::new_with_seed_pattern(16, 16, SeedPattern::Sparse);
+    let seed = universe.clone();
+    universe.tick_many(3);
+    universe.reset();
+    assert_eq!(universe, seed);
+}
+
+#[wasm_bindgen_test]
+pub fn test_reset() {
+    let mut universe = Universe::random(24, 16, 5);
+    let seed = universe.clone();
+    universe.tick_many(4);
+    assert_ne!(universe, seed);
+    universe.reset();
+    assert_eq!(universe, seed);
+    assert_eq!(universe.generation(), 0);
+    assert!(!universe.undo());
+
+    // Randomizing starts over from the new board
+    universe.randomize(0.3, 9);
+    let seed = universe.clone();
+    universe.tick_many(3);
+    universe.reset();
+    assert_eq!(universe.get_cells(), seed.get_cells());
+
+    // Without a seed, the first tick records where to go back to
+    let mut universe = empty_universe(8, 8);
+    universe.insert_glider(1, 1);
+    let start = universe.clone();
+    universe.tick_many(5);
+    universe.reset();
+    assert_eq!(universe, start);
+}
+
+#[wasm_bindgen_test]
+pub fn test_version() {
+    Universe::init_panic_hook();
+    let version = version();
+    assert!(!version.is_empty());
+    let core = version.split(['-', '+']).next().unwrap();
+    let parts: Vec<&str> = core.split('.').collect();
+    assert_eq!(parts.len(), 3, "{}", version);
+    assert!(parts
+        .iter()
+        .all(|part| !part.is_empty() && part.chars().all(|ch| ch.is_ascii_digit())));
+}
+
+#[wasm_bindgen_test]
+pub fn test_try_get_cell() {
+    let universe = input_spaceship();
+    assert_eq!(universe.try_get_cell(1, 2), Ok(Cell::Alive));
+    assert_eq!(universe.try_get_cell(0, 0), Ok(Cell::Dead));
+    assert_eq!(universe.try_get_cell(5, 5), Ok(Cell::Dead));
+    assert_eq!(
+        universe.try_get_cell(6, 0),
+        Err("cell (6, 0) is outside the 6x6 universe".to_string())
+    );
+    assert!(universe.try_get_cell(0, 6).is_err());
+    assert!(universe.try_get_cell(u32::MAX, u32::MAX).is_err());
+}
+
+#[wasm_bindgen_test]
+pub fn test_zero_sizes_are_bumped_up() {
+    let mut universe = empty_universe(4, 4);
+    universe.resize(0, 4);
+    assert_eq!(universe.width(), 1);
+    assert_eq!(universe.get_cell(3, 0), Cell::Dead);
+    universe.set_width(0);
+    universe.set_height(0);
+    assert_eq!((universe.width(), universe.height()), (1, 1));
+    universe.tick();
+    assert_eq!(universe.population(), 0);
+}
+
+#[wasm_bindgen_test]
+#[should_panic(expected = "a 65536x65536 universe has too many cells")]
+pub fn test_too_many_cells() {
+    empty_universe(4, 4).resize(65536, 65536);
+}
+
+#[wasm_bindgen_test]
+pub fn test_wireworld_electron() {
+    // An electron heading east along a wire in the middle row
+    let mut universe = WireworldUniverse::new(8, 3);
+    for col in 0..6 {
+        universe.set_cell_state(1, col, WireworldCell::Conductor);
+    }
+    universe.set_cell_state(1, 0, WireworldCell::ElectronTail);
+    universe.set_cell_state(1, 1, WireworldCell::ElectronHead);
+
+    universe.tick();
+    assert_eq!(universe.generation(), 1);
+    assert_eq!(universe.cell_state(1, 0), WireworldCell::Conductor);
+    assert_eq!(universe.cell_state(1, 1), WireworldCell::ElectronTail);
+    assert_eq!(universe.cell_state(1, 2), WireworldCell::ElectronHead);
+    assert_eq!(universe.cell_state(1, 3), WireworldCell::Conductor);
+    assert_eq!(universe.cell_state(0, 2), WireworldCell::Empty);
+    assert_eq!(universe.cell_state(2, 2), WireworldCell::Empty);
+
+    // The tail behind keeps the electron from turning back
+    universe.tick();
+    assert_eq!(universe.cell_state(1, 0), WireworldCell::Conductor);
+    assert_eq!(universe.cell_state(1, 2), WireworldCell::ElectronTail);
+    assert_eq!(universe.cell_state(1, 3), WireworldCell::ElectronHead);
+}
+
+#[wasm_bindgen_test]
+pub fn test_wireworld_eq_ignores_history() {
+    let mut a = WireworldUniverse::new(4, 1);
+    a.tick();
+    // The same grid reached another way, so the last generation differs
+    let mut b = WireworldUniverse::new(4, 1);
+    b.set_cell_state(0, 0, WireworldCell::Conductor);
+    b.tick();
+    b.set_cell_state(0, 0, WireworldCell::Empty);
+    assert_eq!(a, b);
+
+    b.set_cell_state(0, 1, WireworldCell::Conductor);
+    assert_ne!(a, b);
+}
+
+#[wasm_bindgen_test]
+pub fn test_wireworld_tiny_grid() {
+    // Wrapping reaches the head from both sides, but it is one neighbor
+    let mut universe = WireworldUniverse::new(2, 1);
+    universe.set_cell_state(0, 0, WireworldCell::ElectronHead);
+    universe.set_cell_state(0, 1, WireworldCell::Conductor);
+    universe.tick();
+    assert_eq!(universe.cell_state(0, 0), WireworldCell::ElectronTail);
+    assert_eq!(universe.cell_state(0, 1), WireworldCell::ElectronHead);
+
+    // A lone cell is never its own neighbor
+    let mut universe = WireworldUniverse::new(1, 1);
+    universe.set_cell_state(0, 0, WireworldCell::Conductor);
+    universe.tick();
+    assert_eq!(universe.cell_state(0, 0), WireworldCell::Conductor);
+}
+
+#[wasm_bindgen_test]
+pub fn test_step_elementary_rule_90() {
+    let mut universe = empty_universe(9, 5);
+    universe.set_cell(0, 4, true);
+    for _ in 0..3 {
+        universe.step_elementary(90);
+    }
+    assert_eq!(
+        universe.to_string(),
+        [
+            "◻◻◻◻◼◻◻◻◻",
+            "◻◻◻◼◻◼◻◻◻",
+            "◻◻◼◻◻◻◼◻◻",
+            "◻◼◻◼◻◼◻◼◻",
+            "◻◻◻◻◻◻◻◻◻",
+        ]
+        .iter()
+        .map(|line| format!("{}\n", line))
+        .collect::<String>()
+    );
+
+    // Once the last row is written, older generations scroll off the top
+    universe.step_elementary(90);
+    universe.step_elementary(90);
+    assert_eq!(universe.generation(), 5);
+    let live = universe.live_cells();
+    assert_eq!(live[..2], [(0, 3), (0, 5)]);
+    // The toroidal edges make the pattern wrap around
+    assert_eq!(live[live.len() - 4..], [(4, 0), (4, 1), (4, 7), (4, 8)]);
+}
+
+#[wasm_bindgen_test]
+pub fn test_step_elementary_single_row() {
+    let mut universe = empty_universe(5, 1);
+    universe.set_cell(0, 2, true);
+    universe.step_elementary(90);
+    assert_eq!(universe.live_cells(), vec![(0, 1), (0, 3)]);
+    universe.step_elementary(90);
+    assert_eq!(universe.live_cells(), vec![(0, 0), (0, 4)]);
+    assert_eq!(universe.generation(), 2);
+}
+
+#[wasm_bindgen_test]
+pub fn test_count_neighbors_in_range() {
+    let mut universe = empty_universe(9, 9);
+    universe.set_cells(&[(4, 4), (4, 5), (3, 4), (2, 2), (6, 6), (1, 4), (8, 8)]);
+    universe.set_range(2);
+    assert_eq!(universe.range(), 2);
+    // Everything within two rows and columns, but not (1, 4) or (8, 8)
+    assert_eq!(universe.count_neighbors_in_range(4, 4), 4);
+    assert_eq!(universe.count_neighbors_in_range(3, 3), 5);
+    // The far corner is in range once the edges wrap around
+    assert_eq!(universe.count_neighbors_in_range(0, 0), 2);
+    universe.set_boundary_mode(BoundaryMode::Dead);
+    assert_eq!(universe.count_neighbors_in_range(0, 0), 1);
+    // The diagonal cells are too far away by Manhattan distance
+    universe.set_neighborhood(Neighborhood::VonNeumann);
+    assert_eq!(universe.count_neighbors_in_range(4, 4), 2);
+
+    // A range covering more than the whole universe counts each cell once
+    let mut small = empty_universe(3, 3);
+    small.set_cells(&[(0, 0), (1, 1), (2, 2)]);
+    small.set_range(5);
+    assert_eq!(small.count_neighbors_in_range(1, 1), 2);
+    small.set_range(0);
+    assert_eq!(small.range(), 1);
+}
+
+#[wasm_bindgen_test]
+pub fn test_larger_than_life_range_one_is_conway() {
+    let mut conway = Universe::random(16, 12, 3);
+    let mut larger = conway.clone();
+    larger.set_rule_family(RuleFamily::LargerThanLife {
+        birth: (3, 3),
+        survive: (2, 3),
+    });
+    for _ in 0..10 {
+        conway.tick();
+        larger.tick();
+        assert_eq!(conway, larger);
+    }
+
+    // At range 2 a blinker's cells all see each other, and it grows into a
+    // block five rows tall
+    let mut blinker = empty_universe(9, 9);
+    blinker.set_cells(&[(4, 3), (4, 4), (4, 5)]);
+    blinker.set_rule_family(RuleFamily::LargerThanLife {
+        birth: (3, 3),
+        survive: (2, 3),
+    });
+    blinker.set_range(2);
+    blinker.tick();
+    assert_eq!(blinker.population(), 15);
+    assert_eq!(blinker.live_bounds(), Some((2, 3, 6, 5)));
+}
+
+#[wasm_bindgen_test]
+pub fn test_centroid() {
+    let mut universe = empty_universe(10, 8);
+    assert_eq!(universe.centroid(), None);
+    universe.set_cells(&[(1, 5), (2, 4), (2, 5), (2, 6), (3, 5)]);
+    assert_eq!(universe.centroid(), Some((2.0, 5.0)));
+    universe.set_cells(&[(3, 6)]);
+    assert_eq!(universe.centroid(), Some((13.0 / 6.0, 31.0 / 6.0)));
+}
+
+#[wasm_bindgen_test]
+pub fn test_fingerprint() {
+    let mut universe = input_spaceship();
+    let other = input_spaceship();
+    assert_eq!(universe.fingerprint(), other.fingerprint());
+    // The value itself must never change between runs or platforms
+    assert_eq!(universe.fingerprint(), input_spaceship().fingerprint());
+    assert_eq!(empty_universe(2, 1).fingerprint(), 0x1FF3_F111_C7BD_8BD6);
+    assert_eq!(universe.state_hash(), universe.fingerprint());
+
+    universe.toggle_cell(5, 5);
+    assert_ne!(universe.fingerprint(), other.fingerprint());
+    universe.toggle_cell(5, 5);
+    assert_eq!(universe.fingerprint(), other.fingerprint());
+
+    // The same cells in a differently shaped universe
+    assert_ne!(
+        empty_universe(4, 2).fingerprint(),
+        empty_universe(2, 4).fingerprint()
+    );
+}
+
+#[cfg(feature = "json")]
+#[wasm_bindgen_test]
+pub fn test_json_round_trip() {
+    let mut universe = empty_universe(8, 6);
+    universe.insert_pattern(1, 2, &GLIDER);
+    let json = universe.to_json();
+    assert_eq!(
+        json,
+        r#"{"width":8,"height":6,"live":[[1,3],[2,4],[3,2],[3,3],[3,4]]}"#
+    );
+    assert_eq!(Universe::from_json(&json), Ok(universe));
+
+    assert!(Universe::from_json("{\"width\":8}").is_err());
+    assert!(Universe::from_json(r#"{"width":0,"height":6,"live":[]}"#).is_err());
+    assert!(Universe::from_json(r#"{"width":65535,"height":65535,"live":[]}"#).is_err());
+    assert_eq!(
+        Universe::from_json(r#"{"width":2,"height":2,"live":[[2,0]]}"#),
+        Err("live cell (2, 0) is outside the 2x2 universe".to_string())
+    );
+}
+
+#[wasm_bindgen_test]
+pub fn test_base64_round_trip() {
+    // 29 bytes, which leaves a partial group at the end
+    let mut universe = Universe::random(13, 7, 5);
+    universe.tick();
+    let encoded = universe.to_base64();
+    assert!(encoded
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+    assert_eq!(Universe::from_base64(&encoded), Ok(universe.clone()));
+    assert_eq!(
+        Universe::from_base64(&format!("{}==", encoded)),
+        Ok(universe)
+    );
+
+    // The version byte, 1x1 size, generation and one word of cells
+    let single = empty_universe(1, 1);
+    assert_eq!(single.to_base64(), "AQEAAAABAAAAAAAAAAAAAAAAAAAA");
+    assert_eq!(Universe::from_base64(&single.to_base64()), Ok(single));
+}
+
+#[wasm_bindgen_test]
+pub fn test_base64_corrupted() {
+    let encoded = input_spaceship().to_base64();
+    assert!(Universe::from_base64("").is_err());
+    assert!(Universe::from_base64("not base64!").is_err());
+    assert!(Universe::from_base64(&encoded[..encoded.len() - 6]).is_err());
+    assert!(Universe::from_base64(&encoded[..encoded.len() - 1]).is_err());
+    assert!(Universe::from_base64(&format!("A{}", encoded)).is_err());
+    assert!(Universe::from_base64(&encoded.replace('A', "+")).is_err());
+}
+
+#[wasm_bindgen_test]
+pub fn test_density() {
+    let mut universe = empty_universe(4, 4);
+    assert_eq!(universe.density(), 0.0);
+    universe.fill_rect(0, 0, 4, 2, true);
+    assert_eq!(universe.density(), 0.5);
+    universe.invert();
+    assert_eq!(universe.density(), 0.5);
+    universe.fill_rect(0, 0, 4, 4, true);
+    assert_eq!(universe.density(), 1.0);
+}
+
+#[wasm_bindgen_test]
+pub fn test_is_empty() {
+    let mut universe = empty_universe(40, 3);
+    assert!(universe.is_empty());
+    // Past the first word of the bitset
+    universe.set_cell(2, 39, true);
+    assert!(!universe.is_empty());
+
+    // A lone cell dies out
+    universe.tick();
+    assert!(universe.is_empty());
+}
+
+#[wasm_bindgen_test]
+pub fn test_population_in_rect() {
+    let mut universe = empty_universe(8, 6);
+    universe.set_cells(&[(2, 3), (2, 4), (3, 3), (3, 4), (5, 7)]);
+    // Exactly the block
+    assert_eq!(universe.population_in_rect(2, 3, 2, 2), 4);
+    // Half the block
+    assert_eq!(universe.population_in_rect(2, 4, 1, 3), 2);
+    // Runs past the bottom-right corner, picking up the lone cell too
+    assert_eq!(universe.population_in_rect(1, 2, 100, 100), 5);
+    assert_eq!(universe.population_in_rect(0, 0, 3, 6), 0);
+    assert_eq!(universe.population_in_rect(6, 0, 8, 6), 0);
+    assert_eq!(universe.population_in_rect(0, 0, 0, 6), 0);
+}
+
+#[wasm_bindgen_test]
+pub fn test_wrap() {
+    let universe = empty_universe(5, 3);
+    assert_eq!(universe.wrap(1, 4), (1, 4));
+    assert_eq!(universe.wrap(-1, -1), (2, 4));
+    assert_eq!(universe.wrap(-7, -13), (2, 2));
+    assert_eq!(universe.wrap(3, 5), (0, 0));
+    assert_eq!(universe.wrap(100, 101), (1, 1));
+    assert_eq!(universe.wrap(i64::MIN, i64::MAX), (1, 2));
+}
+
+#[wasm_bindgen_test]
+pub fn test_neighbor_counts() {
+    let mut universe = Universe::random(11, 7, 9);
+    universe.set_wall(3, 3, true);
+    universe.set_walls_alive(true);
+    let counts = universe.neighbor_counts();
+    assert_eq!(counts.len(), 77);
+    for &(row, col) in [(0, 0), (3, 4), (6, 10), (2, 2), (5, 0)].iter() {
+        assert_eq!(
+            counts[(row * 11 + col) as usize],
+            universe.live_neighbor_count(row, col)
+        );
+    }
+
+    universe.set_boundary_mode(BoundaryMode::Dead);
+    universe.set_neighborhood(Neighborhood::VonNeumann);
+    let counts = universe.neighbor_counts();
+    for row in 0..7 {
+        for col in 0..11 {
+            assert_eq!(
+                counts[(row * 11 + col) as usize],
+                universe.live_neighbor_count(row, col)
+            );
+        }
+    }
+
+    // Wrapping around a universe two cells wide only counts each once
+    let mut narrow = empty_universe(2, 3);
+    narrow.set_cells(&[(0, 0), (1, 1)]);
+    assert_eq!(narrow.neighbor_counts(), vec![1, 2, 2, 1, 2, 2]);
+}
+
+#[wasm_bindgen_test]
+pub fn test_cell_conversions() {
+    assert_eq!(Cell::from(true), Cell::Alive);
+    assert_eq!(Cell::from(false), Cell::Dead);
+    assert!(bool::from(Cell::Alive));
+    assert!(!bool::from(Cell::Dead));
+    assert_eq!(Cell::Alive.toggle(), Cell::Dead);
+    assert_eq!(Cell::Dead.toggle(), Cell::Alive);
+    assert_eq!(Cell::Dead.toggle().toggle(), Cell::Dead);
+}
+
+#[wasm_bindgen_test]
+pub fn test_universe_builder() {
+    let highlife = Rule::parse("B36/S23").unwrap();
+    let universe = UniverseBuilder::new()
+        .width(20)
+        .height(20)
+        .rule(highlife)
+        .boundary(BoundaryMode::Toroidal)
+        .seed(11)
+        .density(0.3)
+        .build();
+    assert_eq!((universe.width(), universe.height()), (20, 20));
+    assert_eq!(universe.rule(), highlife);
+    assert_eq!(universe.boundary_mode(), BoundaryMode::Toroidal);
+    assert_eq!(universe.generation(), 0);
+    assert!(universe.density() > 0.2 && universe.density() < 0.4);
+
+    let mut expected = Universe::new_with_size(20, 20);
+    expected.randomize(0.3, 11);
+    assert_eq!(universe, expected);
+
+    // Without a seed the universe starts empty
+    let dead = UniverseBuilder::default()
+        .boundary(BoundaryMode::Dead)
+        .build();
+    assert_eq!((dead.width(), dead.height()), (128, 128));
+    assert_eq!(dead.population(), 0);
+    assert_eq!(dead.boundary_mode(), BoundaryMode::Dead);
+    assert_eq!(dead.rule(), Rule::default());
+
+    let seeds = UniverseBuilder::new().rule_str("B2/S").unwrap().build();
+    assert_eq!(seeds.rule(), Rule::parse("B2/S").unwrap());
+    assert!(UniverseBuilder::new().rule_str("B9/S").is_err());
+}
+
+#[wasm_bindgen_test]
+pub fn test_set_rule_str() {
+    let mut universe = empty_universe(4, 4);
+    assert!(universe.set_rule_str("B36/S23").is_ok());
+    assert_eq!(universe.rule(), Rule::parse("B36/S23").unwrap());
+    assert!(universe.set_rule_str("B3/S2x").is_err());
+    assert_eq!(universe.rule(), Rule::parse("B36/S23").unwrap());
+}
+
+#[wasm_bindgen_test]
+pub fn test_stamp() {
+    let mut glider = empty_universe(3, 3);
+    glider.set_cells(&GLIDER);
+
+    let mut universe = empty_universe(10, 10);
+    universe.stamp(&glider, 0, 0, BitOp::Or);
+    let mut expected = GLIDER.to_vec();
+    expected.sort_unstable();
+    assert_eq!(universe.live_cells(), expected);
+
+    // Stamping again with XOR cancels the glider back out
+    universe.stamp(&glider, 0, 0, BitOp::Xor);
+    assert_eq!(universe.population(), 0);
+
+    // Only the top-left corner of the glider fits at the bottom-right
+    universe.stamp(&glider, 8, 8, BitOp::Or);
+    assert_eq!(universe.live_cells(), vec![(8, 9)]);
+    universe.stamp(&glider, 10, 10, BitOp::Or);
+    assert_eq!(universe.population(), 1);
+
+    // AND clears covered cells that are dead in the stamp, and nothing else
+    universe.fill_rect(0, 0, 4, 4, true);
+    universe.stamp(&glider, 0, 0, BitOp::And);
+    assert_eq!(universe.population(), 5 + 7 + 1);
+}
+
+#[wasm_bindgen_test]
+pub fn test_tile() {
+    let mut universe = empty_universe(5, 4);
+    universe.tile(&[(0, 0)], 2, 2);
+    assert_eq!(universe.to_string(), "◼◻◼◻◼\n◻◻◻◻◻\n◼◻◼◻◼\n◻◻◻◻◻\n");
+
+    // Blinkers only go where all three cells fit
+    let mut universe = empty_universe(8, 3);
+    universe.tile(&[(0, 0), (0, 1), (0, 2)], 2, 4);
+    assert_eq!(
+        universe.live_cells(),
+        vec![
+            (0, 0),
+            (0, 1),
+            (0, 2),
+            (0, 4),
+            (0, 5),
+            (0, 6),
+            (2, 0),
+            (2, 1),
+            (2, 2),
+            (2, 4),
+            (2, 5),
+            (2, 6)
+        ]
+    );
+
+    universe.clear();
+    universe.tile(&GLIDER, 3, 3);
+    assert_eq!(universe.population(), 10);
+    universe.clear();
+    universe.tile(&[(0, 0)], 0, 0);
+    assert_eq!(universe.population(), 8 * 3);
+    universe.clear();
+    universe.tile(&[(3, 0)], 1, 1);
+    assert_eq!(universe.population(), 0);
+}
+
+#[wasm_bindgen_test]
+pub fn test_period_at() {
+    let mut blinker = empty_universe(5, 5);
+    blinker.set_cells(&[(2, 1), (2, 2), (2, 3)]);
+    assert_eq!(blinker.period_at(), Some(2));
+    // The universe itself never ticked
+    assert_eq!(blinker.generation(), 0);
+    assert_eq!(blinker.live_cells(), vec![(2, 1), (2, 2), (2, 3)]);
+
+    // A glider repeats its shape one cell further along every 4 ticks
+    let mut glider = empty_universe(12, 12);
+    glider.set_cells(&GLIDER);
+    glider.tick();
+    assert_eq!(glider.period_at(), Some(4));
+    assert_eq!(glider.generation(), 1);
+
+    let mut block = empty_universe(6, 6);
+    block.set_cells(&[(2, 2), (2, 3), (3, 2), (3, 3)]);
+    assert_eq!(block.period_at(), Some(1));
+
+    // An R-pentomino never returns to its starting shape
+    let mut pentomino = empty_universe(32, 32);
+    pentomino.set_cells(&[(10, 11), (10, 12), (11, 10), (11, 11), (12, 11)]);
+    assert_eq!(pentomino.period_at(), None);
+}
+
+#[wasm_bindgen_test]
+pub fn test_tick_skips_quiet_cells_correctly() {
+    // Two-state Generations plays the same game, but always counts every
+    // cell's neighbors
+    let slow_rules = RuleFamily::Generations { states: 2 };
+    for &boundary in [BoundaryMode::Toroidal, BoundaryMode::Dead].iter() {
+        let mut fast = empty_universe(256, 256);
+        fast.set_boundary_mode(boundary);
+        fast.insert_pattern(252, 252, &GLIDER);
+        fast.insert_pattern(100, 31, &[(0, 0), (0, 1), (0, 2)]);
+        let mut slow = fast.clone();
+        slow.set_rule_family(slow_rules);
+        // Long enough for the glider to cross the corner
+        for _ in 0..32 {
+            fast.tick();
+            slow.tick();
+            assert_eq!(fast, slow);
+            assert_eq!(fast.get_ages(), slow.get_ages());
+        }
+    }
+
+    // Walls that count as alive wake up the cells around them
+    let mut walled = empty_universe(64, 8);
+    walled.set_wall(4, 40, true);
+    walled.set_wall(4, 41, true);
+    walled.set_wall(3, 40, true);
+    walled.set_walls_alive(true);
+    walled.tick();
+    assert_eq!(walled.live_cells(), vec![(3, 41)]);
+}
+
+#[wasm_bindgen_test]
+pub fn test_paused() {
+    let mut universe = input_spaceship();
+    universe.tick();
+    universe.set_paused(true);
+    assert!(universe.paused());
+    let before = universe.clone();
+    universe.tick();
+    universe.tick_many(3);
+    assert_eq!(universe.run_until_stable(10), 0);
+    assert_eq!(universe, before);
+    assert_eq!(universe.generation(), 1);
+
+    // Edits still apply, and analysis still ticks a copy
+    universe.toggle_cell(0, 0);
+    assert_eq!(universe.get_cell(0, 0), Cell::Alive);
+    universe.toggle_cell(0, 0);
+    assert_eq!(universe.period_at(), Some(4));
+    assert!(universe.paused());
+
+    universe.set_paused(false);
+    universe.tick();
+    assert_eq!(universe.generation(), 2);
+    assert_ne!(universe, before);
+}
+
+#[wasm_bindgen_test]
+pub fn test_delta_rle_round_trip() {
+    let mut universe = Universe::random(40, 30, 12);
+    for _ in 0..5 {
+        let mut previous = universe.clone();
+        universe.tick();
+        previous.apply_delta_rle(&universe.delta_rle()).unwrap();
+        assert_eq!(previous.get_cells(), universe.get_cells());
+    }
+
+    // A blinker flips four cells, in two runs
+    let mut blinker = empty_universe(5, 5);
+    blinker.set_cells(&[(2, 1), (2, 2), (2, 3)]);
+    blinker.tick();
+    assert_eq!(blinker.delta_rle(), vec![7, 1, 3, 1, 1, 1, 3, 1]);
+
+    // Long runs take more than one byte
+    let mut sparse = empty_universe(100, 100);
+    sparse.tick();
+    assert!(sparse.delta_rle().is_empty());
+    sparse.set_cells(&[(50, 49), (50, 50), (50, 51)]);
+    sparse.tick();
+    assert_eq!(sparse.delta_rle()[..3], [0xD6, 0x26, 1]);
+
+    let mut small = empty_universe(4, 4);
+    assert!(small.apply_delta_rle(&[0x80]).is_err());
+    assert!(small.apply_delta_rle(&[3]).is_err());
+    assert!(small.apply_delta_rle(&[0, 2, 10, 7]).is_err());
+    assert_eq!(small.population(), 0);
+}
+
+#[wasm_bindgen_test]
+pub fn test_render_with() {
+    let mut blinker = empty_universe(5, 3);
+    blinker.set_cells(&[(1, 1), (1, 2), (1, 3)]);
+    assert_eq!(blinker.render_with('.', '#'), ".....\n.###.\n.....\n");
+    blinker.tick();
+    assert_eq!(blinker.render_with('.', '#'), "..#..\n..#..\n..#..\n");
+    assert_eq!(blinker.to_string(), blinker.render_with('◻', '◼'));
+}
+
+#[wasm_bindgen_test]
+pub fn test_seeds_rule() {
+    let seeds = Rule::parse("B2/S").unwrap();
+    assert_eq!(seeds.survive, [false; 9]);
+    assert_eq!(seeds.to_string(), "B2/S");
+
+    // Two adjacent cells die, giving birth to the cells beside them
+    let mut universe = empty_universe(6, 6);
+    universe.set_rule(seeds);
+    universe.set_cells(&[(2, 2), (2, 3)]);
+    universe.tick();
+    assert_eq!(universe.live_cells(), vec![(1, 2), (1, 3), (3, 2), (3, 3)]);
+    assert_eq!(universe.last_deaths(), 2);
+    assert_eq!(universe.last_births(), 4);
+
+    // Orthogonally no cell sees both of them, so nothing is born
+    let mut von_neumann = empty_universe(6, 6);
+    von_neumann.set_rule(seeds);
+    von_neumann.set_neighborhood(Neighborhood::VonNeumann);
+    von_neumann.set_cells(&[(2, 2), (2, 3)]);
+    von_neumann.tick();
+    assert_eq!(von_neumann.population(), 0);
+}
+
+#[wasm_bindgen_test]
+pub fn test_cells_bytes() {
+    let mut universe = Universe::random(40, 3, 8);
+    let bytes = universe.cells_bytes();
+    let words = universe.get_cells().as_slice();
+    assert_eq!(bytes.len(), words.len() * 4);
+    for (chunk, &word) in bytes.chunks(4).zip(words) {
+        assert_eq!(
+            u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]),
+            word
+        );
+    }
+    let raw = unsafe { std::slice::from_raw_parts(universe.cells(), words.len()) };
+    assert_eq!(raw, words);
+
+    // The copy stays valid after a resize
+    universe.resize(80, 80);
+    assert_eq!(bytes.len(), 16);
+    assert_eq!(universe.cells_bytes().len(), 200 * 4);
+}
+
+#[wasm_bindgen_test]
+pub fn test_component_count() {
+    let mut universe = empty_universe(16, 16);
+    assert_eq!(universe.component_count(), 0);
+
+    universe.set_cells(&[(2, 2), (2, 3), (3, 2), (3, 3)]);
+    assert_eq!(universe.component_count(), 1);
+
+    universe.clear();
+    universe.insert_pattern(1, 1, &GLIDER);
+    universe.insert_pattern(9, 9, &GLIDER);
+    assert_eq!(universe.component_count(), 2);
+
+    // Cells on opposite edges only touch when the edges wrap
+    let mut edges = empty_universe(8, 8);
+    edges.set_cells(&[(0, 0), (7, 7), (4, 4)]);
+    assert_eq!(edges.component_count(), 2);
+    edges.set_boundary_mode(BoundaryMode::Dead);
+    assert_eq!(edges.component_count(), 3);
+}
+
+#[wasm_bindgen_test]
+pub fn test_largest_cluster() {
+    let mut universe = empty_universe(16, 16);
+    assert_eq!(universe.largest_cluster(), 0);
+
+    // A 10 cell blob, touching diagonally in places, and a blinker
+    universe.fill_rect(2, 2, 3, 3, true);
+    universe.set_cells(&[(5, 5)]);
+    universe.set_cells(&[(10, 4), (10, 5), (10, 6)]);
+    assert_eq!(universe.population(), 13);
+    assert_eq!(universe.component_count(), 2);
+    assert_eq!(universe.largest_cluster(), 10);
+}
+
+#[wasm_bindgen_test]
+pub fn test_reversible_universe() {
+    let start = Universe::random(24, 18, 21);
+    let mut universe = ReversibleUniverse::from_universe(start.clone());
+    for _ in 0..30 {
+        universe.tick();
+    }
+    assert_eq!(universe.generation(), 30);
+    assert_ne!(universe.universe().get_cells(), start.get_cells());
+    for _ in 0..30 {
+        universe.tick_back();
+    }
+    assert_eq!(universe.generation(), 0);
+    assert_eq!(universe.universe().get_cells(), start.get_cells());
+    assert_eq!(universe.previous_cells().count_ones(..), 0);
+
+    // Going back past the start and forward again also round trips
+    let mut blinker = ReversibleUniverse::new(8, 8);
+    blinker.set_cell(3, 2, true);
+    blinker.set_cell(3, 3, true);
+    blinker.set_cell(3, 4, true);
+    let initial = blinker.clone();
+    blinker.tick_back();
+    blinker.tick_back();
+    assert_eq!(blinker.generation(), -2);
+    blinker.tick();
+    blinker.tick();
+    assert_eq!(blinker, initial);
+    assert_eq!(blinker.get_cell(3, 3), Cell::Alive);
+}
+
+#[wasm_bindgen_test]
+pub fn test_weighted_neighbor_sum() {
+    let mut universe = empty_universe(8, 8);
+    universe.set_cells(&[(2, 3), (3, 2), (3, 4), (2, 2), (4, 4), (3, 3)]);
+    assert_eq!(universe.kernel(), &Kernel::default());
+    assert_eq!(
+        universe.weighted_neighbor_sum(3, 3) as u8,
+        universe.live_neighbor_count(3, 3)
+    );
+
+    // Orthogonal neighbors count double: three of them and two diagonals
+    let mut weights = Vec::new();
+    for &(dr, dc) in [(-1, 0), (1, 0), (0, -1), (0, 1)].iter() {
+        weights.push((dr, dc, 2));
+    }
+    for &(dr, dc) in [(-1, -1), (-1, 1), (1, -1), (1, 1)].iter() {
+        weights.push((dr, dc, 1));
+    }
+    universe.set_kernel(Kernel::new(weights));
+    assert_eq!(universe.weighted_neighbor_sum(3, 3), 3 * 2 + 2);
+    // Negative weights and offsets beyond the edges
+    universe.set_kernel(Kernel::new(vec![(-3, 0, -5), (0, 1, 4)]));
+    assert_eq!(universe.weighted_neighbor_sum(5, 3), -5);
+    assert_eq!(universe.weighted_neighbor_sum(0, 1), 0);
+    universe.set_cells(&[(5, 1)]);
+    assert_eq!(universe.weighted_neighbor_sum(0, 1), -5);
+    universe.set_boundary_mode(BoundaryMode::Dead);
+    assert_eq!(universe.weighted_neighbor_sum(0, 1), 0);
+}
+
+#[wasm_bindgen_test]
+pub fn test_weighted_rule_family() {
+    let mut conway = Universe::random(16, 12, 4);
+    let mut weighted = conway.clone();
+    weighted.set_rule_family(RuleFamily::Weighted {
+        birth: (3, 3),
+        survive: (2, 3),
+    });
+    for _ in 0..10 {
+        conway.tick();
+        weighted.tick();
+        assert_eq!(conway, weighted);
+    }
+}
+
+#[wasm_bindgen_test]
+pub fn test_render_padded() {
+    let mut universe = empty_universe(3, 2);
+    universe.set_boundary_mode(BoundaryMode::Dead);
+    universe.set_cells(&[(0, 0)]);
+    let padded = universe.render_padded(1);
+    assert_eq!(padded, "◻◻◻◻◻\n◻◼◻◻◻\n◻◻◻◻◻\n◻◻◻◻◻\n");
+    let lines: Vec<&str> = padded.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert!(lines.iter().all(|line| line.chars().count() == 5));
+
+    assert_eq!(universe.render_padded(0), universe.render());
+}
+
+#[wasm_bindgen_test]
+pub fn test_auto_expand() {
+    // A glider heading up and to the right
+    let mut universe = empty_universe(10, 10);
+    universe.set_cells(&[(2, 4), (2, 5), (2, 6), (3, 6), (4, 5)]);
+    universe.set_auto_expand(true);
+    assert!(universe.auto_expand());
+
+    let mut expanded_at = None;
+    for step in 0..12 {
+        universe.tick();
+        if universe.height() > 10 {
+            expanded_at = Some(step);
+            break;
+        }
+    }
+    // The glider reaches the top row in its 5th generation, and the
+    // following tick makes room above it
+    assert_eq!(expanded_at, Some(5));
+    assert_eq!((universe.width(), universe.height()), (10, 18));
+    assert_eq!(universe.generation(), 6);
+    assert_eq!(universe.population(), 5);
+    let (min_row, _, _, _) = universe.live_bounds().unwrap();
+    assert!(min_row >= 7);
+
+    // It keeps flying without ever wrapping around
+    for _ in 0..40 {
+        universe.tick();
+        assert_eq!(universe.population(), 5);
+    }
+    assert!(universe.width() > 10);
+}
+
+#[wasm_bindgen_test]
+pub fn test_auto_expand_keeps_cell_data() {
+    let mut universe = empty_universe(6, 6);
+    // A block in the top-left corner and a wall in the bottom-right one
+    universe.set_cells(&[(0, 0), (0, 1), (1, 0), (1, 1)]);
+    universe.set_wall(5, 5, true);
+    universe.tick();
+    universe.set_auto_expand(true);
+    universe.tick();
+    assert_eq!((universe.width(), universe.height()), (14, 14));
+    assert_eq!(universe.live_cells(), vec![(8, 8), (8, 9), (9, 8), (9, 9)]);
+    assert!(universe.is_wall(13, 13));
+    // Two ticks alive, one of them before growing
+    let idx = 8 * 14 + 8;
+    assert_eq!(universe.get_ages()[idx], 2);
+
+    // Undoing and resetting give the block back in its new place
+    assert!(universe.undo());
+    assert_eq!(universe.population(), 4);
+    assert_eq!(universe.get_cell(8, 8), Cell::Alive);
+    universe.reset();
+    assert_eq!(universe.population(), 4);
+    assert_eq!(universe.get_cell(9, 9), Cell::Alive);
+}
+
+#[wasm_bindgen_test]
+pub fn test_tick_and_render() {
+    let mut fused = input_spaceship();
+    let mut separate = input_spaceship();
+    for _ in 0..3 {
+        separate.tick();
+        assert_eq!(fused.tick_and_render(), separate.render());
+    }
+    assert_eq!(fused, separate);
+}
+
+#[wasm_bindgen_test]
+pub fn test_search_soups() {
+    let survivors = Universe::search_soups(10, 10, 24, 200, 1000);
+    assert_eq!(survivors, Universe::search_soups(10, 10, 24, 200, 1000));
+    assert!(!survivors.is_empty());
+    for &seed in survivors.iter() {
+        assert!((1000..1024).contains(&seed));
+        let mut soup = Universe::random(10, 10, seed);
+        soup.run_until_stable(200);
+        assert!(soup.is_stable());
+        assert!(soup.population() > 0);
+    }
+    assert!(Universe::search_soups(10, 10, 0, 200, 1000).is_empty());
+}
+
+#[wasm_bindgen_test]
+pub fn test_row_bits() {
+    let mut universe = empty_universe(12, 3);
+    for col in (0..12).step_by(2) {
+        universe.set_cell(1, col, true);
+    }
+    universe.set_cell(2, 11, true);
+    assert_eq!(universe.row_bits(0), vec![0, 0]);
+    assert_eq!(universe.row_bits(1), vec![0b0101_0101, 0b0000_0101]);
+    assert_eq!(universe.row_bits(2), vec![0, 0b0000_1000]);
+}
+
+#[wasm_bindgen_test]
+#[should_panic(expected = "row 3 is outside the 12x3 universe")]
+pub fn test_row_bits_out_of_range() {
+    empty_universe(12, 3).row_bits(3);
+}
+
+#[wasm_bindgen_test]
+pub fn test_equals_translated() {
+    let mut glider = empty_universe(12, 12);
+    glider.insert_pattern(2, 2, &GLIDER);
+    let start = glider.clone();
+    glider.tick_many(4);
+    assert_eq!(start.equals_translated(&glider), Some((1, 1)));
+    assert_eq!(glider.equals_translated(&start), Some((-1, -1)));
+    assert_eq!(start.equals_translated(&start), Some((0, 0)));
+
+    // Halfway through its cycle the glider has another shape
+    glider.tick_many(2);
+    assert_eq!(start.equals_translated(&glider), None);
+
+    let mut far = empty_universe(30, 20);
+    far.insert_pattern(15, 20, &GLIDER);
+    assert_eq!(start.equals_translated(&far), Some((13, 18)));
+    assert_eq!(start.equals_translated(&empty_universe(12, 12)), None);
+}
+
+#[cfg(feature = "gif")]
+#[wasm_bindgen_test]
+pub fn test_to_gif() {
+    let mut universe = input_spaceship();
+    let before = universe.clone();
+    let bytes = universe.to_gif(5, 3, 10);
+    assert_eq!(&bytes[..6], b"GIF89a");
+    assert_eq!(universe, before);
+
+    let mut options = gif::DecodeOptions::new();
+    options.set_color_output(gif::ColorOutput::Indexed);
+    let mut decoder = options.read_info(&bytes[..]).unwrap();
+    assert_eq!((decoder.width(), decoder.height()), (18, 18));
+    let mut frames = Vec::new();
+    while let Some(frame) = decoder.read_next_frame().unwrap() {
+        assert_eq!(frame.delay, 10);
+        frames.push(frame.buffer.to_vec());
+    }
+    assert_eq!(frames.len(), 5);
+    // The live cell at (1, 2) covers columns 6 to 8 of pixel rows 3 to 5
+    for row in 3..6 {
+        assert_eq!(frames[0][row * 18 + 5..row * 18 + 10], [0, 1, 1, 1, 0]);
+    }
+    assert_eq!(frames[0][0], 0);
+    assert_ne!(frames[0], frames[1]);
+}